@@ -0,0 +1,92 @@
+/// Renders a triangle into an offscreen texture and saves it as `capture.png`,
+/// without creating a window.
+fn main() {
+    env_logger::init();
+
+    let size = wgpu::Extent3d {
+        width: 256,
+        height: 256,
+        depth: 1,
+    };
+
+    let instance = wgpu::Instance::new();
+    let adapter = instance.get_adapter(&wgpu::AdapterDescriptor {
+        power_preference: wgpu::PowerPreference::Default,
+    });
+    let mut device = adapter.create_device(&wgpu::DeviceDescriptor {
+        extensions: wgpu::Extensions {
+            anisotropic_filtering: false,
+        },
+    });
+
+    let vs_words = wgpu::include_spirv!("examples/hello_triangle/shader.vert.spv");
+    let vs_module = device.create_shader_module(vs_words);
+    let fs_words = wgpu::include_spirv!("examples/hello_triangle/shader.frag.spv");
+    let fs_module = device.create_shader_module(fs_words);
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[],
+    });
+    let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("triangle"),
+        layout: &pipeline_layout,
+        vertex_stage: wgpu::PipelineStageDescriptor {
+            module: &vs_module,
+            entry_point: "main",
+            specialization: &[],
+        },
+        fragment_stage: Some(wgpu::PipelineStageDescriptor {
+            module: &fs_module,
+            entry_point: "main",
+            specialization: &[],
+        }),
+        rasterization_state: wgpu::RasterizationStateDescriptor {
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: wgpu::CullMode::None,
+            depth_bias: 0,
+            depth_bias_slope_scale: 0.0,
+            depth_bias_clamp: 0.0,
+        },
+        primitive_topology: wgpu::PrimitiveTopology::TriangleList,
+        color_states: &[wgpu::ColorStateDescriptor {
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            color: wgpu::BlendDescriptor::REPLACE,
+            alpha: wgpu::BlendDescriptor::REPLACE,
+            write_mask: wgpu::ColorWriteFlags::ALL,
+        }],
+        depth_stencil_state: None,
+        index_format: wgpu::IndexFormat::Uint16,
+        vertex_buffers: &[],
+        sample_count: 1,
+    });
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("capture"),
+        size,
+        array_size: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8UnormSrgb,
+        usage: wgpu::TextureUsageFlags::OUTPUT_ATTACHMENT | wgpu::TextureUsageFlags::TRANSFER_SRC,
+    });
+    let view = texture.create_default_view();
+
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    {
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: &view,
+                load_op: wgpu::LoadOp::Clear,
+                store_op: wgpu::StoreOp::Store,
+                clear_color: wgpu::Color::GREEN,
+            }],
+            depth_stencil_attachment: None,
+        });
+        rpass.set_pipeline(&render_pipeline);
+        rpass.draw(0 .. 3, 0 .. 1);
+    }
+    device.get_queue().submit(&[encoder.finish()]);
+
+    texture.save_png(&mut device, "capture.png").unwrap();
+}
@@ -1,7 +1,10 @@
+use wgpu::VertexLayout;
+
 #[path = "../framework.rs"]
 mod framework;
 
-#[derive(Clone, Copy)]
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
 struct Vertex {
     _pos: [f32; 4],
     _tex_coord: [f32; 2],
@@ -106,13 +109,10 @@ impl Example {
 
 impl framework::Example for Example {
     fn init(sc_desc: &wgpu::SwapChainDescriptor, device: &mut wgpu::Device) -> Self {
-        use std::mem;
-
         let mut init_encoder =
-            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { todo: 0 });
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
 
         // Create the vertex and index buffers
-        let vertex_size = mem::size_of::<Vertex>();
         let (vertex_data, index_data) = create_vertices();
         let vertex_buf = device
             .create_buffer_mapped(vertex_data.len(), wgpu::BufferUsageFlags::VERTEX)
@@ -124,6 +124,7 @@ impl framework::Example for Example {
 
         // Create pipeline layout
         let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
             bindings: &[
                 wgpu::BindGroupLayoutBinding {
                     binding: 0,
@@ -143,6 +144,7 @@ impl framework::Example for Example {
             ],
         });
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
             bind_group_layouts: &[&bind_group_layout],
         });
 
@@ -155,6 +157,7 @@ impl framework::Example for Example {
             depth: 1,
         };
         let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
             size: texture_extent,
             array_size: 1,
             dimension: wgpu::TextureDimension::D2,
@@ -187,17 +190,8 @@ impl framework::Example for Example {
 
         // Create other resources
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            r_address_mode: wgpu::AddressMode::ClampToEdge,
-            s_address_mode: wgpu::AddressMode::ClampToEdge,
-            t_address_mode: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Nearest,
             min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            lod_min_clamp: -100.0,
-            lod_max_clamp: 100.0,
-            max_anisotropy: 0,
-            compare_function: wgpu::CompareFunction::Always,
-            border_color: wgpu::BorderColor::TransparentBlack,
+            ..Default::default()
         });
         let mx_total = Self::generate_matrix(sc_desc.width as f32 / sc_desc.height as f32);
         let mx_ref: &[f32; 16] = mx_total.as_ref();
@@ -210,6 +204,7 @@ impl framework::Example for Example {
 
         // Create bind group
         let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
             layout: &bind_group_layout,
             bindings: &[
                 wgpu::Binding {
@@ -231,55 +226,30 @@ impl framework::Example for Example {
         });
 
         // Create the render pipeline
-        let vs_bytes = framework::load_glsl(include_str!("shader.vert"), framework::ShaderStage::Vertex);
-        let fs_bytes = framework::load_glsl(include_str!("shader.frag"), framework::ShaderStage::Fragment);
+        let vs_bytes = framework::load_glsl(include_str!("shader.vert"), wgpu::ShaderStage::Vertex);
+        let fs_bytes =
+            framework::load_glsl(include_str!("shader.frag"), wgpu::ShaderStage::Fragment);
         let vs_module = device.create_shader_module(&vs_bytes);
         let fs_module = device.create_shader_module(&fs_bytes);
 
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            layout: &pipeline_layout,
-            vertex_stage: wgpu::PipelineStageDescriptor {
+        let pipeline = wgpu::RenderPipelineBuilder::new(
+            &pipeline_layout,
+            wgpu::PipelineStageDescriptor {
                 module: &vs_module,
                 entry_point: "main",
+                specialization: &[],
             },
-            fragment_stage: wgpu::PipelineStageDescriptor {
-                module: &fs_module,
-                entry_point: "main",
-            },
-            rasterization_state: wgpu::RasterizationStateDescriptor {
-                front_face: wgpu::FrontFace::Cw,
-                cull_mode: wgpu::CullMode::Back,
-                depth_bias: 0,
-                depth_bias_slope_scale: 0.0,
-                depth_bias_clamp: 0.0,
-            },
-            primitive_topology: wgpu::PrimitiveTopology::TriangleList,
-            color_states: &[wgpu::ColorStateDescriptor {
-                format: sc_desc.format,
-                color: wgpu::BlendDescriptor::REPLACE,
-                alpha: wgpu::BlendDescriptor::REPLACE,
-                write_mask: wgpu::ColorWriteFlags::ALL,
-            }],
-            depth_stencil_state: None,
-            index_format: wgpu::IndexFormat::Uint16,
-            vertex_buffers: &[wgpu::VertexBufferDescriptor {
-                stride: vertex_size as u32,
-                step_mode: wgpu::InputStepMode::Vertex,
-                attributes: &[
-                    wgpu::VertexAttributeDescriptor {
-                        attribute_index: 0,
-                        format: wgpu::VertexFormat::Float4,
-                        offset: 0,
-                    },
-                    wgpu::VertexAttributeDescriptor {
-                        attribute_index: 1,
-                        format: wgpu::VertexFormat::Float2,
-                        offset: 4 * 4,
-                    },
-                ],
-            }],
-            sample_count: 1,
-        });
+        )
+        .fragment_stage(wgpu::PipelineStageDescriptor {
+            module: &fs_module,
+            entry_point: "main",
+            specialization: &[],
+        })
+        .front_face(wgpu::FrontFace::Cw)
+        .cull_mode(wgpu::CullMode::Back)
+        .color_target(sc_desc.format)
+        .vertex_buffer(Vertex::buffer_descriptor())
+        .build(&device);
 
         // Done
         let init_command_buf = init_encoder.finish();
@@ -307,14 +277,14 @@ impl framework::Example for Example {
             .fill_from_slice(mx_ref);
 
         let mut encoder =
-            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { todo: 0 });
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
         encoder.copy_buffer_to_buffer(&temp_buf, 0, &self.uniform_buf, 0, 64);
         device.get_queue().submit(&[encoder.finish()]);
     }
 
     fn render(&mut self, frame: &wgpu::SwapChainOutput, device: &mut wgpu::Device) {
         let mut encoder =
-            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { todo: 0 });
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
         {
             let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
@@ -8,26 +8,8 @@ pub fn cast_slice<T>(data: &[T]) -> &[u8] {
     unsafe { from_raw_parts(data.as_ptr() as *const u8, data.len() * size_of::<T>()) }
 }
 
-#[allow(dead_code)]
-pub enum ShaderStage {
-    Vertex,
-    Fragment,
-    Compute,
-}
-
-pub fn load_glsl(code: &str, stage: ShaderStage) -> Vec<u8> {
-    use std::io::Read;
-
-    let ty = match stage {
-        ShaderStage::Vertex => glsl_to_spirv::ShaderType::Vertex,
-        ShaderStage::Fragment => glsl_to_spirv::ShaderType::Fragment,
-        ShaderStage::Compute => glsl_to_spirv::ShaderType::Compute,
-    };
-
-    let mut output = glsl_to_spirv::compile(&code, ty).unwrap();
-    let mut spv = Vec::new();
-    output.read_to_end(&mut spv).unwrap();
-    spv
+pub fn load_glsl(code: &str, stage: wgpu::ShaderStage) -> Vec<u32> {
+    wgpu::compile_glsl(code, stage, &[]).unwrap_or_else(|err| panic!("{}", err))
 }
 
 pub trait Example {
@@ -71,6 +53,7 @@ pub fn run<E: Example>(title: &str) {
 
     let surface = instance.create_surface(&window);
     let mut sc_desc = wgpu::SwapChainDescriptor {
+        label: None,
         usage: wgpu::TextureUsageFlags::OUTPUT_ATTACHMENT,
         format: wgpu::TextureFormat::Bgra8Unorm,
         width: size.width.round() as u32,
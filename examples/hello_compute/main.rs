@@ -24,8 +24,8 @@ fn main() {
         },
     });
 
-    let cs_bytes = include_bytes!("shader.comp.spv");
-    let cs_module = device.create_shader_module(cs_bytes);
+    let cs_words = wgpu::include_spirv!("examples/hello_compute/shader.comp.spv");
+    let cs_module = device.create_shader_module(cs_words);
 
     let staging_buffer = device
         .create_buffer_mapped(
@@ -37,6 +37,7 @@ fn main() {
         .fill_from_slice(&numbers);
 
     let storage_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
         size,
         usage: wgpu::BufferUsageFlags::STORAGE
             | wgpu::BufferUsageFlags::TRANSFER_DST
@@ -44,6 +45,7 @@ fn main() {
     });
 
     let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: None,
         bindings: &[wgpu::BindGroupLayoutBinding {
             binding: 0,
             visibility: wgpu::ShaderStageFlags::COMPUTE,
@@ -52,6 +54,7 @@ fn main() {
     });
 
     let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
         layout: &bind_group_layout,
         bindings: &[wgpu::Binding {
             binding: 0,
@@ -63,18 +66,22 @@ fn main() {
     });
 
     let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
         bind_group_layouts: &[&bind_group_layout],
     });
 
     let compute_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: None,
         layout: &pipeline_layout,
         compute_stage: wgpu::PipelineStageDescriptor {
             module: &cs_module,
             entry_point: "main",
+            specialization: &[],
         },
     });
 
-    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { todo: 0 });
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
     encoder.copy_buffer_to_buffer(&staging_buffer, 0, &storage_buffer, 0, size);
     {
         let mut cpass = encoder.begin_compute_pass();
@@ -11,31 +11,39 @@ fn main() {
         },
     });
 
-    let vs_bytes = include_bytes!("shader.vert.spv");
-    let vs_module = device.create_shader_module(vs_bytes);
-    let fs_bytes = include_bytes!("shader.frag.spv");
-    let fs_module = device.create_shader_module(fs_bytes);
+    let vs_words = wgpu::include_spirv!("examples/hello_triangle/shader.vert.spv");
+    let vs_module = device.create_shader_module(vs_words);
+    let fs_words = wgpu::include_spirv!("examples/hello_triangle/shader.frag.spv");
+    let fs_module = device.create_shader_module(fs_words);
 
     let bind_group_layout =
-        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor { bindings: &[] });
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            bindings: &[],
+        });
     let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
         layout: &bind_group_layout,
         bindings: &[],
     });
     let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
         bind_group_layouts: &[&bind_group_layout],
     });
 
     let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: None,
         layout: &pipeline_layout,
         vertex_stage: wgpu::PipelineStageDescriptor {
             module: &vs_module,
             entry_point: "main",
+            specialization: &[],
         },
-        fragment_stage: wgpu::PipelineStageDescriptor {
+        fragment_stage: Some(wgpu::PipelineStageDescriptor {
             module: &fs_module,
             entry_point: "main",
-        },
+            specialization: &[],
+        }),
         rasterization_state: wgpu::RasterizationStateDescriptor {
             front_face: wgpu::FrontFace::Ccw,
             cull_mode: wgpu::CullMode::None,
@@ -77,6 +85,7 @@ fn main() {
     let mut swap_chain = device.create_swap_chain(
         &surface,
         &wgpu::SwapChainDescriptor {
+            label: None,
             usage: wgpu::TextureUsageFlags::OUTPUT_ATTACHMENT,
             format: wgpu::TextureFormat::Bgra8Unorm,
             width: size.width.round() as u32,
@@ -107,7 +116,7 @@ fn main() {
 
         let frame = swap_chain.get_next_texture();
         let mut encoder =
-            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { todo: 0 });
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
         {
             let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
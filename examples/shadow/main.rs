@@ -4,10 +4,13 @@ use std::{
     rc::Rc,
 };
 
+use wgpu::{Uniform, VertexLayout};
+
 #[path = "../framework.rs"]
 mod framework;
 
-#[derive(Clone, Copy)]
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
 struct Vertex {
     _pos: [i8; 4],
     _normal: [i8; 4],
@@ -99,7 +102,7 @@ struct Light {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Uniform)]
 struct LightRaw {
     proj: [[f32; 4]; 4],
     pos: [f32; 4],
@@ -127,20 +130,22 @@ impl Light {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Uniform)]
 struct ForwardUniforms {
     proj: [[f32; 4]; 4],
     num_lights: [u32; 4],
 }
 
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Uniform)]
 struct EntityUniforms {
+    #[uniform(glsl = "mat4")]
     model: cgmath::Matrix4<f32>,
     color: [f32; 4],
 }
 
 #[repr(C)]
+#[derive(Clone, Copy, Uniform)]
 struct ShadowUniforms {
     proj: [[f32; 4]; 4],
 }
@@ -185,7 +190,6 @@ impl Example {
 impl framework::Example for Example {
     fn init(sc_desc: &wgpu::SwapChainDescriptor, device: &mut wgpu::Device) -> Self {
         // Create the vertex and index buffers
-        let vertex_size = mem::size_of::<Vertex>();
         let (cube_vertex_data, cube_index_data) = create_cube();
         let cube_vertex_buf = Rc::new(
             device
@@ -210,12 +214,14 @@ impl framework::Example for Example {
 
         let entity_uniform_size = mem::size_of::<EntityUniforms>() as u32;
         let plane_uniform_buf = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
             size: entity_uniform_size,
             usage: wgpu::BufferUsageFlags::UNIFORM | wgpu::BufferUsageFlags::TRANSFER_DST,
         });
 
         let local_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
                 bindings: &[wgpu::BindGroupLayoutBinding {
                     binding: 0,
                     visibility: wgpu::ShaderStageFlags::VERTEX | wgpu::ShaderStageFlags::FRAGMENT,
@@ -227,6 +233,7 @@ impl framework::Example for Example {
             use cgmath::SquareMatrix;
 
             let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
                 layout: &local_bind_group_layout,
                 bindings: &[wgpu::Binding {
                     binding: 0,
@@ -290,6 +297,7 @@ impl framework::Example for Example {
                 scale: cube.scale,
             };
             let uniform_buf = device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
                 size: entity_uniform_size,
                 usage: wgpu::BufferUsageFlags::UNIFORM | wgpu::BufferUsageFlags::TRANSFER_DST,
             });
@@ -301,6 +309,7 @@ impl framework::Example for Example {
                 index_buf: Rc::clone(&cube_index_buf),
                 index_count: cube_index_data.len(),
                 bind_group: device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: None,
                     layout: &local_bind_group_layout,
                     bindings: &[wgpu::Binding {
                         binding: 0,
@@ -316,20 +325,14 @@ impl framework::Example for Example {
 
         // Create other resources
         let shadow_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            r_address_mode: wgpu::AddressMode::ClampToEdge,
-            s_address_mode: wgpu::AddressMode::ClampToEdge,
-            t_address_mode: wgpu::AddressMode::ClampToEdge,
             mag_filter: wgpu::FilterMode::Linear,
             min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            lod_min_clamp: -100.0,
-            lod_max_clamp: 100.0,
-            max_anisotropy: 0,
             compare_function: wgpu::CompareFunction::LessEqual,
-            border_color: wgpu::BorderColor::TransparentBlack,
+            ..Default::default()
         });
 
         let shadow_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
             size: Self::SHADOW_SIZE,
             array_size: Self::MAX_LIGHTS as u32,
             dimension: wgpu::TextureDimension::D2,
@@ -339,17 +342,7 @@ impl framework::Example for Example {
         let shadow_view = shadow_texture.create_default_view();
 
         let mut shadow_target_views = (0..2)
-            .map(|i| {
-                Some(shadow_texture.create_view(&wgpu::TextureViewDescriptor {
-                    format: Self::SHADOW_FORMAT,
-                    dimension: wgpu::TextureViewDimension::D2,
-                    aspect: wgpu::TextureAspectFlags::DEPTH,
-                    base_mip_level: 0,
-                    level_count: 1,
-                    base_array_layer: i as u32,
-                    array_count: 1,
-                }))
-            })
+            .map(|i| Some(shadow_texture.layer_view(i)))
             .collect::<Vec<_>>();
         let lights = vec![
             Light {
@@ -379,33 +372,20 @@ impl framework::Example for Example {
         ];
         let light_uniform_size = (Self::MAX_LIGHTS * mem::size_of::<LightRaw>()) as u32;
         let light_uniform_buf = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
             size: light_uniform_size,
             usage: wgpu::BufferUsageFlags::UNIFORM
                 | wgpu::BufferUsageFlags::TRANSFER_SRC
                 | wgpu::BufferUsageFlags::TRANSFER_DST,
         });
 
-        let vb_desc = wgpu::VertexBufferDescriptor {
-            stride: vertex_size as u32,
-            step_mode: wgpu::InputStepMode::Vertex,
-            attributes: &[
-                wgpu::VertexAttributeDescriptor {
-                    attribute_index: 0,
-                    format: wgpu::VertexFormat::Char4,
-                    offset: 0,
-                },
-                wgpu::VertexAttributeDescriptor {
-                    attribute_index: 1,
-                    format: wgpu::VertexFormat::Char4,
-                    offset: 4 * 1,
-                },
-            ],
-        };
+        let vb_desc = Vertex::buffer_descriptor();
 
         let shadow_pass = {
             // Create pipeline layout
             let bind_group_layout =
                 device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: None,
                     bindings: &[wgpu::BindGroupLayoutBinding {
                         binding: 0, // global
                         visibility: wgpu::ShaderStageFlags::VERTEX,
@@ -413,17 +393,20 @@ impl framework::Example for Example {
                     }],
                 });
             let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: None,
                 bind_group_layouts: &[&bind_group_layout, &local_bind_group_layout],
             });
 
             let uniform_size = mem::size_of::<ShadowUniforms>() as u32;
             let uniform_buf = device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
                 size: uniform_size,
                 usage: wgpu::BufferUsageFlags::UNIFORM | wgpu::BufferUsageFlags::TRANSFER_DST,
             });
 
             // Create bind group
             let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
                 layout: &bind_group_layout,
                 bindings: &[wgpu::Binding {
                     binding: 0,
@@ -437,47 +420,28 @@ impl framework::Example for Example {
             // Create the render pipeline
             let vs_bytes = framework::load_glsl(
                 include_str!("bake.vert"),
-                framework::ShaderStage::Vertex,
-            );
-            let fs_bytes = framework::load_glsl(
-                include_str!("bake.frag"),
-                framework::ShaderStage::Fragment,
+                wgpu::ShaderStage::Vertex,
             );
             let vs_module = device.create_shader_module(&vs_bytes);
-            let fs_module = device.create_shader_module(&fs_bytes);
 
-            let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                layout: &pipeline_layout,
-                vertex_stage: wgpu::PipelineStageDescriptor {
+            let pipeline = wgpu::RenderPipelineBuilder::new(
+                &pipeline_layout,
+                wgpu::PipelineStageDescriptor {
                     module: &vs_module,
                     entry_point: "main",
+                    specialization: &[],
                 },
-                fragment_stage: wgpu::PipelineStageDescriptor {
-                    module: &fs_module,
-                    entry_point: "main",
-                },
-                rasterization_state: wgpu::RasterizationStateDescriptor {
-                    front_face: wgpu::FrontFace::Cw,
-                    cull_mode: wgpu::CullMode::Back,
-                    depth_bias: 2, // corresponds to bilinear filtering
-                    depth_bias_slope_scale: 2.0,
-                    depth_bias_clamp: 0.0,
-                },
-                primitive_topology: wgpu::PrimitiveTopology::TriangleList,
-                color_states: &[],
-                depth_stencil_state: Some(wgpu::DepthStencilStateDescriptor {
-                    format: Self::SHADOW_FORMAT,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::LessEqual,
-                    stencil_front: wgpu::StencilStateFaceDescriptor::IGNORE,
-                    stencil_back: wgpu::StencilStateFaceDescriptor::IGNORE,
-                    stencil_read_mask: 0,
-                    stencil_write_mask: 0,
-                }),
-                index_format: wgpu::IndexFormat::Uint16,
-                vertex_buffers: &[vb_desc.clone()],
-                sample_count: 1,
-            });
+            )
+            .rasterization_state(wgpu::RasterizationStateDescriptor {
+                front_face: wgpu::FrontFace::Cw,
+                cull_mode: wgpu::CullMode::Back,
+                depth_bias: 2, // corresponds to bilinear filtering
+                depth_bias_slope_scale: 2.0,
+                depth_bias_clamp: 0.0,
+            })
+            .depth_test(Self::SHADOW_FORMAT, wgpu::CompareFunction::LessEqual)
+            .vertex_buffer(vb_desc.clone())
+            .build(&device);
 
             Pass {
                 pipeline,
@@ -490,6 +454,7 @@ impl framework::Example for Example {
             // Create pipeline layout
             let bind_group_layout =
                 device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: None,
                     bindings: &[
                         wgpu::BindGroupLayoutBinding {
                             binding: 0, // global
@@ -516,6 +481,7 @@ impl framework::Example for Example {
                     ],
                 });
             let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: None,
                 bind_group_layouts: &[&bind_group_layout, &local_bind_group_layout],
             });
 
@@ -534,6 +500,7 @@ impl framework::Example for Example {
 
             // Create bind group
             let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
                 layout: &bind_group_layout,
                 bindings: &[
                     wgpu::Binding {
@@ -564,52 +531,34 @@ impl framework::Example for Example {
             // Create the render pipeline
             let vs_bytes = framework::load_glsl(
                 include_str!("forward.vert"),
-                framework::ShaderStage::Vertex,
+                wgpu::ShaderStage::Vertex,
             );
             let fs_bytes = framework::load_glsl(
                 include_str!("forward.frag"),
-                framework::ShaderStage::Fragment,
+                wgpu::ShaderStage::Fragment,
             );
             let vs_module = device.create_shader_module(&vs_bytes);
             let fs_module = device.create_shader_module(&fs_bytes);
 
-            let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                layout: &pipeline_layout,
-                vertex_stage: wgpu::PipelineStageDescriptor {
+            let pipeline = wgpu::RenderPipelineBuilder::new(
+                &pipeline_layout,
+                wgpu::PipelineStageDescriptor {
                     module: &vs_module,
                     entry_point: "main",
+                    specialization: &[],
                 },
-                fragment_stage: wgpu::PipelineStageDescriptor {
-                    module: &fs_module,
-                    entry_point: "main",
-                },
-                rasterization_state: wgpu::RasterizationStateDescriptor {
-                    front_face: wgpu::FrontFace::Cw,
-                    cull_mode: wgpu::CullMode::Back,
-                    depth_bias: 0,
-                    depth_bias_slope_scale: 0.0,
-                    depth_bias_clamp: 0.0,
-                },
-                primitive_topology: wgpu::PrimitiveTopology::TriangleList,
-                color_states: &[wgpu::ColorStateDescriptor {
-                    format: sc_desc.format,
-                    color: wgpu::BlendDescriptor::REPLACE,
-                    alpha: wgpu::BlendDescriptor::REPLACE,
-                    write_mask: wgpu::ColorWriteFlags::ALL,
-                }],
-                depth_stencil_state: Some(wgpu::DepthStencilStateDescriptor {
-                    format: Self::DEPTH_FORMAT,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Less,
-                    stencil_front: wgpu::StencilStateFaceDescriptor::IGNORE,
-                    stencil_back: wgpu::StencilStateFaceDescriptor::IGNORE,
-                    stencil_read_mask: 0,
-                    stencil_write_mask: 0,
-                }),
-                index_format: wgpu::IndexFormat::Uint16,
-                vertex_buffers: &[vb_desc],
-                sample_count: 1,
-            });
+            )
+            .fragment_stage(wgpu::PipelineStageDescriptor {
+                module: &fs_module,
+                entry_point: "main",
+                specialization: &[],
+            })
+            .front_face(wgpu::FrontFace::Cw)
+            .cull_mode(wgpu::CullMode::Back)
+            .color_target(sc_desc.format)
+            .depth_test(Self::DEPTH_FORMAT, wgpu::CompareFunction::Less)
+            .vertex_buffer(vb_desc)
+            .build(&device);
 
             Pass {
                 pipeline,
@@ -619,6 +568,7 @@ impl framework::Example for Example {
         };
 
         let depth_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
             size: wgpu::Extent3d {
                 width: sc_desc.width,
                 height: sc_desc.height,
@@ -654,12 +604,13 @@ impl framework::Example for Example {
                 .fill_from_slice(mx_ref);
 
             let mut encoder =
-                device.create_command_encoder(&wgpu::CommandEncoderDescriptor { todo: 0 });
+                device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
             encoder.copy_buffer_to_buffer(&temp_buf, 0, &self.forward_pass.uniform_buf, 0, 64);
             device.get_queue().submit(&[encoder.finish()]);
         }
 
         let depth_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
             size: wgpu::Extent3d {
                 width: sc_desc.width,
                 height: sc_desc.height,
@@ -675,7 +626,7 @@ impl framework::Example for Example {
 
     fn render(&mut self, frame: &wgpu::SwapChainOutput, device: &mut wgpu::Device) {
         let mut encoder =
-            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { todo: 0 });
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
 
         {
             let size = mem::size_of::<EntityUniforms>() as u32;
@@ -0,0 +1,144 @@
+use crate::{TextureFormat, VertexFormat};
+
+/// Static properties of a `TextureFormat`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TextureFormatInfo {
+    /// Size in bytes of one block of texels.
+    pub block_size: u32,
+    /// Width and height in texels of one block, `(1, 1)` for uncompressed formats.
+    pub block_dimensions: (u32, u32),
+    /// Number of channels, including depth and stencil.
+    pub components: u32,
+    pub depth: bool,
+    pub stencil: bool,
+    /// Color values are sRGB encoded and converted to linear on sampling.
+    pub srgb: bool,
+    /// Channels hold unnormalized integers (`Uint` or `Sint` formats).
+    pub integer: bool,
+}
+
+impl TextureFormatInfo {
+    fn color(block_size: u32, components: u32) -> Self {
+        TextureFormatInfo {
+            block_size,
+            block_dimensions: (1, 1),
+            components,
+            depth: false,
+            stencil: false,
+            srgb: false,
+            integer: false,
+        }
+    }
+
+    fn srgb(block_size: u32, components: u32) -> Self {
+        TextureFormatInfo {
+            srgb: true,
+            ..Self::color(block_size, components)
+        }
+    }
+
+    fn integer(block_size: u32, components: u32) -> Self {
+        TextureFormatInfo {
+            integer: true,
+            ..Self::color(block_size, components)
+        }
+    }
+
+    fn depth_stencil(block_size: u32, stencil: bool) -> Self {
+        TextureFormatInfo {
+            depth: true,
+            stencil,
+            ..Self::color(block_size, if stencil { 2 } else { 1 })
+        }
+    }
+
+    /// Returns true if the format holds color data, as opposed to depth or stencil.
+    pub fn is_color(&self) -> bool {
+        !self.depth && !self.stencil
+    }
+}
+
+/// Metadata queries for `TextureFormat`.
+pub trait TextureFormatExt {
+    fn info(&self) -> TextureFormatInfo;
+}
+
+impl TextureFormatExt for TextureFormat {
+    fn info(&self) -> TextureFormatInfo {
+        use crate::TextureFormat::*;
+        match *self {
+            // Normal 8 bit formats
+            R8Unorm | R8Snorm => TextureFormatInfo::color(1, 1),
+            R8UnormSrgb => TextureFormatInfo::srgb(1, 1),
+            R8Uint | R8Sint => TextureFormatInfo::integer(1, 1),
+            // Normal 16 bit formats
+            R16Unorm | R16Snorm | R16Float => TextureFormatInfo::color(2, 1),
+            R16Uint | R16Sint => TextureFormatInfo::integer(2, 1),
+            Rg8Unorm | Rg8Snorm => TextureFormatInfo::color(2, 2),
+            Rg8UnormSrgb => TextureFormatInfo::srgb(2, 2),
+            Rg8Uint | Rg8Sint => TextureFormatInfo::integer(2, 2),
+            // Packed 16 bit formats
+            B5g6r5Unorm => TextureFormatInfo::color(2, 3),
+            // Normal 32 bit formats
+            R32Float => TextureFormatInfo::color(4, 1),
+            R32Uint | R32Sint => TextureFormatInfo::integer(4, 1),
+            Rg16Unorm | Rg16Snorm | Rg16Float => TextureFormatInfo::color(4, 2),
+            Rg16Uint | Rg16Sint => TextureFormatInfo::integer(4, 2),
+            Rgba8Unorm | Rgba8Snorm | Bgra8Unorm => TextureFormatInfo::color(4, 4),
+            Rgba8UnormSrgb | Bgra8UnormSrgb => TextureFormatInfo::srgb(4, 4),
+            Rgba8Uint | Rgba8Sint => TextureFormatInfo::integer(4, 4),
+            // Packed 32 bit formats
+            Rgb10a2Unorm => TextureFormatInfo::color(4, 4),
+            Rg11b10Float => TextureFormatInfo::color(4, 3),
+            // Normal 64 bit formats
+            Rg32Float => TextureFormatInfo::color(8, 2),
+            Rg32Uint | Rg32Sint => TextureFormatInfo::integer(8, 2),
+            Rgba16Unorm | Rgba16Snorm | Rgba16Float => TextureFormatInfo::color(8, 4),
+            Rgba16Uint | Rgba16Sint => TextureFormatInfo::integer(8, 4),
+            // Normal 128 bit formats
+            Rgba32Float => TextureFormatInfo::color(16, 4),
+            Rgba32Uint | Rgba32Sint => TextureFormatInfo::integer(16, 4),
+            // Depth and stencil formats
+            D16Unorm => TextureFormatInfo::depth_stencil(2, false),
+            D32Float => TextureFormatInfo::depth_stencil(4, false),
+            D24UnormS8Uint => TextureFormatInfo::depth_stencil(4, true),
+            D32FloatS8Uint => TextureFormatInfo::depth_stencil(8, true),
+        }
+    }
+}
+
+/// Metadata queries for `VertexFormat`.
+pub trait VertexFormatExt {
+    /// Size in bytes of one attribute of this format.
+    fn size(&self) -> u32;
+    /// Number of components of one attribute of this format.
+    fn components(&self) -> u32;
+}
+
+impl VertexFormatExt for VertexFormat {
+    fn size(&self) -> u32 {
+        use crate::VertexFormat::*;
+        match *self {
+            Uchar2 | Char2 | Uchar2Norm | Char2Norm => 2,
+            Uchar4 | Char4 | Uchar4Norm | Char4Norm => 4,
+            Ushort2 | Short2 | Ushort2Norm | Short2Norm | Half2 => 4,
+            Ushort4 | Short4 | Ushort4Norm | Short4Norm | Half4 => 8,
+            Float | Uint | Int => 4,
+            Float2 | Uint2 | Int2 => 8,
+            Float3 | Uint3 | Int3 => 12,
+            Float4 | Uint4 | Int4 => 16,
+        }
+    }
+
+    fn components(&self) -> u32 {
+        use crate::VertexFormat::*;
+        match *self {
+            Float | Uint | Int => 1,
+            Uchar2 | Char2 | Uchar2Norm | Char2Norm | Ushort2 | Short2 | Ushort2Norm
+            | Short2Norm | Half2 | Float2 | Uint2 | Int2 => 2,
+            Float3 | Uint3 | Int3 => 3,
+            Uchar4 | Char4 | Uchar4Norm | Char4Norm | Ushort4 | Short4 | Ushort4Norm
+            | Short4Norm | Half4 | Float4 | Uint4 | Int4 => 4,
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{read_spirv, Device, ShaderModule, ShaderStage};
+use wgpu_shader::{inject_defines, PreprocessedSource};
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single message reported by the GLSL compiler.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Source string number, as set by `#line` directives. The main source is 0.
+    pub file: u32,
+    /// Name of the source file, when compiled from a `PreprocessedSource`.
+    pub file_name: Option<String>,
+    /// Line number within that source, if the message refers to one.
+    pub line: Option<u32>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match (self.line, &self.file_name) {
+            (Some(line), Some(name)) => {
+                write!(f, "{}:{}: {}: {}", name, line, severity, self.message)
+            }
+            (Some(line), None) => {
+                write!(f, "{}:{}: {}: {}", self.file, line, severity, self.message)
+            }
+            (None, _) => write!(f, "{}: {}", severity, self.message),
+        }
+    }
+}
+
+/// Failure to compile GLSL source to SPIR-V.
+#[derive(Clone, Debug)]
+pub struct GlslError {
+    pub diagnostics: Vec<Diagnostic>,
+    /// Complete compiler output, for messages that could not be parsed.
+    pub log: String,
+}
+
+impl fmt::Display for GlslError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.diagnostics.is_empty() {
+            return write!(f, "{}", self.log.trim());
+        }
+        for (i, diagnostic) in self.diagnostics.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", diagnostic)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for GlslError {}
+
+impl GlslError {
+    fn from_log(log: String) -> Self {
+        let diagnostics = log.lines().filter_map(parse_diagnostic).collect();
+        GlslError { diagnostics, log }
+    }
+}
+
+/// Parse a glslang message of the form `ERROR: 0:12: 'foo' : undeclared identifier`.
+fn parse_diagnostic(line: &str) -> Option<Diagnostic> {
+    let (severity, rest) = if let Some(rest) = line.strip_prefix("ERROR: ") {
+        (Severity::Error, rest)
+    } else if let Some(rest) = line.strip_prefix("WARNING: ") {
+        (Severity::Warning, rest)
+    } else {
+        return None;
+    };
+
+    let mut parts = rest.splitn(3, ':');
+    let file = parts.next().and_then(|file| file.trim().parse().ok());
+    let line = parts.next().and_then(|line| line.trim().parse().ok());
+    match (file, line, parts.next()) {
+        (Some(file), Some(line), Some(message)) => {
+            let message = message.trim();
+            // glslang follows the first error with a note that carries no information.
+            if message == "'' : compilation terminated" {
+                return None;
+            }
+            Some(Diagnostic {
+                severity,
+                file,
+                file_name: None,
+                line: Some(line),
+                message: message.to_string(),
+            })
+        }
+        // Summaries like "2 compilation errors.  No code generated." repeat the above.
+        _ if rest.contains("compilation errors") => None,
+        _ => Some(Diagnostic {
+            severity,
+            file: 0,
+            file_name: None,
+            line: None,
+            message: rest.trim().to_string(),
+        }),
+    }
+}
+
+fn compile(source: &str, stage: ShaderStage) -> Result<Vec<u32>, GlslError> {
+    let ty = match stage {
+        ShaderStage::Vertex => glsl_to_spirv::ShaderType::Vertex,
+        ShaderStage::Fragment => glsl_to_spirv::ShaderType::Fragment,
+        ShaderStage::Compute => glsl_to_spirv::ShaderType::Compute,
+    };
+    let output = glsl_to_spirv::compile(source, ty).map_err(GlslError::from_log)?;
+    read_spirv(output).map_err(|err| GlslError {
+        diagnostics: Vec::new(),
+        log: err.to_string(),
+    })
+}
+
+/// Compile GLSL source to SPIR-V, with `defines` given as name and value pairs.
+pub fn compile_glsl(
+    source: &str,
+    stage: ShaderStage,
+    defines: &[(&str, &str)],
+) -> Result<Vec<u32>, GlslError> {
+    compile(&inject_defines(source, defines), stage)
+}
+
+/// Compile the output of `preprocess` to SPIR-V. Diagnostics are labelled with the
+/// names of the files they refer to.
+pub fn compile_preprocessed_glsl(
+    source: &PreprocessedSource,
+    stage: ShaderStage,
+) -> Result<Vec<u32>, GlslError> {
+    compile(&source.source, stage).map_err(|mut err| {
+        for diagnostic in &mut err.diagnostics {
+            if diagnostic.line.is_some() {
+                diagnostic.file_name = source.file_name(diagnostic.file).map(str::to_string);
+            }
+        }
+        err
+    })
+}
+
+impl Device {
+    /// Compile GLSL source and create a shader module from it, returning the compiler
+    /// diagnostics on failure.
+    pub fn create_shader_module_from_glsl(
+        &self,
+        source: &str,
+        stage: ShaderStage,
+        defines: &[(&str, &str)],
+    ) -> Result<ShaderModule, GlslError> {
+        let spv = compile_glsl(source, stage, defines)?;
+        Ok(self.create_shader_module(&spv))
+    }
+}
@@ -0,0 +1,151 @@
+use crate::readback::align_row_pitch;
+use crate::{
+    BufferCopyView,
+    BufferUsageFlags,
+    CommandEncoder,
+    Device,
+    Extent3d,
+    Origin3d,
+    Texture,
+    TextureCopyView,
+    TextureDescriptor,
+    TextureDimension,
+    TextureFormat,
+    TextureReadError,
+    TextureUsageFlags,
+};
+
+use image::{png::PNGEncoder, ColorType, DynamicImage, GenericImageView};
+
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum SaveImageError {
+    /// The texture contents could not be read back.
+    Read(TextureReadError),
+    /// The image file could not be written.
+    Io(io::Error),
+}
+
+impl Device {
+    /// Create a 2D texture with the contents of `image`, recording the upload into `encoder`.
+    ///
+    /// Color images are assumed to be sRGB encoded and get an sRGB format, while
+    /// grayscale images are treated as linear data. `TRANSFER_DST` is always added
+    /// to the requested `usage`.
+    pub fn create_texture_from_image(
+        &self,
+        encoder: &mut CommandEncoder,
+        image: &DynamicImage,
+        usage: TextureUsageFlags,
+    ) -> Texture {
+        let (width, height) = image.dimensions();
+        let (format, texel_size, texels) = match *image {
+            DynamicImage::ImageLuma8(_) => (TextureFormat::R8Unorm, 1, image.raw_pixels()),
+            DynamicImage::ImageLumaA8(_) => (TextureFormat::Rg8Unorm, 2, image.raw_pixels()),
+            DynamicImage::ImageBgra8(_) => (TextureFormat::Bgra8UnormSrgb, 4, image.raw_pixels()),
+            // There are no 3 channel formats, so everything else is expanded to RGBA.
+            DynamicImage::ImageRgb8(_)
+            | DynamicImage::ImageRgba8(_)
+            | DynamicImage::ImageBgr8(_) => {
+                (TextureFormat::Rgba8UnormSrgb, 4, image.to_rgba().into_raw())
+            }
+        };
+
+        let size = Extent3d {
+            width,
+            height,
+            depth: 1,
+        };
+        let texture = self.create_texture(&TextureDescriptor {
+            label: None,
+            size,
+            array_size: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: usage | TextureUsageFlags::TRANSFER_DST,
+        });
+
+        let row_size = texel_size * width;
+        let row_pitch = align_row_pitch(row_size);
+        let staging = self.create_buffer_mapped::<u8>(
+            (row_pitch * height) as usize,
+            BufferUsageFlags::TRANSFER_SRC,
+        );
+        for (dst, src) in staging
+            .data
+            .chunks_mut(row_pitch as usize)
+            .zip(texels.chunks(row_size as usize))
+        {
+            dst[.. row_size as usize].copy_from_slice(src);
+        }
+        let staging_buffer = staging.finish();
+
+        encoder.copy_buffer_to_texture(
+            BufferCopyView {
+                buffer: &staging_buffer,
+                offset: 0,
+                row_pitch,
+                image_height: height,
+            },
+            TextureCopyView {
+                texture: &texture,
+                level: 0,
+                slice: 0,
+                origin: Origin3d {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+            },
+            size,
+        );
+
+        texture
+    }
+}
+
+impl Texture {
+    /// Read back the first mip level of the first array slice and write it as a PNG file.
+    /// Only the first depth slice of a 3D texture is saved.
+    ///
+    /// Only 8 bit per channel formats with one, two or four channels can be saved.
+    pub fn save_png<P: AsRef<Path>>(
+        &self,
+        device: &mut Device,
+        path: P,
+    ) -> Result<(), SaveImageError> {
+        let (color, texel_size) = match self.format {
+            TextureFormat::R8Unorm | TextureFormat::R8UnormSrgb => (ColorType::Gray(8), 1),
+            TextureFormat::Rg8Unorm | TextureFormat::Rg8UnormSrgb => (ColorType::GrayA(8), 2),
+            TextureFormat::Rgba8Unorm
+            | TextureFormat::Rgba8UnormSrgb
+            | TextureFormat::Bgra8Unorm
+            | TextureFormat::Bgra8UnormSrgb => (ColorType::RGBA(8), 4),
+            other => {
+                return Err(SaveImageError::Read(TextureReadError::UnsupportedFormat(
+                    other,
+                )));
+            }
+        };
+
+        let mut texels = self
+            .read_to_vec(device, 0, 0)
+            .map_err(SaveImageError::Read)?;
+        // 3D textures are read back with all of their depth slices.
+        let row_size = texel_size * self.size.width;
+        texels.truncate((row_size * self.size.height) as usize);
+        if let TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb = self.format {
+            for texel in texels.chunks_mut(4) {
+                texel.swap(0, 2);
+            }
+        }
+
+        let file = File::create(path).map_err(SaveImageError::Io)?;
+        PNGEncoder::new(file)
+            .encode(&texels, self.size.width, self.size.height, color)
+            .map_err(SaveImageError::Io)
+    }
+}
@@ -1,10 +1,60 @@
 use arrayvec::ArrayVec;
 
+use std::collections::HashMap;
 use std::ffi::CString;
+use std::fmt;
 use std::ops::Range;
 use std::ptr;
 use std::slice;
-
+use std::sync::Mutex;
+
+mod format;
+#[cfg(feature = "glsl")]
+mod glsl;
+#[cfg(feature = "image")]
+mod image_io;
+pub mod loader;
+mod object_cache;
+mod pipeline_builder;
+mod readback;
+mod reflect;
+mod spirv;
+mod uniform;
+pub mod util;
+mod vertex;
+mod view;
+
+pub use crate::format::{TextureFormatExt, TextureFormatInfo, VertexFormatExt};
+#[cfg(feature = "glsl")]
+pub use crate::glsl::{compile_glsl, compile_preprocessed_glsl, Diagnostic, GlslError, Severity};
+#[cfg(feature = "image")]
+pub use crate::image_io::SaveImageError;
+pub use crate::object_cache::ObjectCacheStats;
+pub use crate::pipeline_builder::{
+    default_color_state,
+    default_depth_stencil_state,
+    default_rasterization_state,
+    RenderPipelineBuilder,
+};
+pub use crate::readback::{TextureReadError, COPY_ROW_PITCH_ALIGNMENT};
+pub use crate::reflect::{
+    merge_bindings,
+    reflect_spirv,
+    ReflectError,
+    ReflectedBinding,
+    ReflectedPipelineLayout,
+    ReflectedVertexLayout,
+    ShaderReflection,
+    VertexInput,
+};
+pub use crate::spirv::specialize_spirv;
+#[doc(hidden)]
+pub use crate::uniform::{align_to as __uniform_align_to, max as __uniform_max};
+pub use crate::uniform::{Uniform, UniformLayout};
+pub use crate::vertex::VertexLayout;
+pub use crate::view::{CubeFace, TextureViewBuilder};
+
+#[cfg(feature = "window-winit")]
 pub use wgn::winit;
 pub use wgn::{
     AdapterDescriptor,
@@ -15,13 +65,11 @@ pub use wgn::{
     BlendFactor,
     BlendOperation,
     BorderColor,
-    BufferDescriptor,
     BufferMapAsyncStatus,
     BufferUsageFlags,
     Color,
     ColorStateDescriptor,
     ColorWriteFlags,
-    CommandEncoderDescriptor,
     CompareFunction,
     CullMode,
     DepthStencilStateDescriptor,
@@ -39,24 +87,36 @@ pub use wgn::{
     RasterizationStateDescriptor,
     RenderPassColorAttachmentDescriptor,
     RenderPassDepthStencilAttachmentDescriptor,
-    SamplerDescriptor,
     ShaderAttributeIndex,
-    ShaderModuleDescriptor,
     ShaderStageFlags,
     StencilOperation,
     StencilStateFaceDescriptor,
     StoreOp,
-    SwapChainDescriptor,
     TextureAspectFlags,
-    TextureDescriptor,
     TextureDimension,
     TextureFormat,
     TextureUsageFlags,
-    TextureViewDescriptor,
     TextureViewDimension,
     VertexAttributeDescriptor,
     VertexFormat,
 };
+#[cfg(all(feature = "macros", feature = "glsl"))]
+pub use wgpu_macros::include_glsl;
+#[cfg(feature = "derive")]
+pub use wgpu_macros::{Uniform, VertexLayout};
+#[cfg(feature = "macros")]
+pub use wgpu_macros::include_spirv;
+pub use wgpu_shader::{
+    preprocess,
+    read_spirv,
+    validate_spirv,
+    FileResolver,
+    IncludeResolver,
+    IncludedSource,
+    PreprocessError,
+    PreprocessedSource,
+    SpirvError,
+};
 
 //TODO: avoid heap allocating vectors during resource creation.
 #[derive(Default)]
@@ -66,6 +126,53 @@ struct Temp {
     command_buffers: Vec<wgn::CommandBufferId>,
 }
 
+/// Formats an object label for error messages.
+struct DisplayLabel<'a>(Option<&'a str>);
+
+impl<'a> DisplayLabel<'a> {
+    fn of(label: &'a Option<String>) -> Self {
+        DisplayLabel(label.as_ref().map(String::as_str))
+    }
+}
+
+impl<'a> fmt::Display for DisplayLabel<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(label) => write!(f, "{:?}", label),
+            None => write!(f, "<unlabeled>"),
+        }
+    }
+}
+
+macro_rules! impl_label {
+    ($($ty:ident),*) => {
+        $(
+            impl $ty {
+                /// The label given when the object was created.
+                pub fn label(&self) -> Option<&str> {
+                    self.label.as_ref().map(String::as_str)
+                }
+            }
+        )*
+    };
+}
+
+impl_label!(
+    Buffer,
+    Texture,
+    TextureView,
+    Sampler,
+    SwapChain,
+    BindGroupLayout,
+    BindGroup,
+    ShaderModule,
+    PipelineLayout,
+    RenderPipeline,
+    ComputePipeline,
+    CommandBuffer,
+    CommandEncoder
+);
+
 pub struct Instance {
     id: wgn::InstanceId,
 }
@@ -77,40 +184,80 @@ pub struct Adapter {
 pub struct Device {
     id: wgn::DeviceId,
     temp: Temp,
+    /// Modules patched by `specialize_spirv`, created once per module and set of
+    /// constants. They live as long as the device, as wgpu-native cannot destroy
+    /// shader modules.
+    specialized_modules: Mutex<HashMap<SpecializedModuleKey, wgn::ShaderModuleId>>,
+    /// Created on first use by `empty_fragment_module`.
+    empty_fragment_module: Mutex<Option<wgn::ShaderModuleId>>,
+    objects: Option<Mutex<object_cache::ObjectCache>>,
+}
+
+/// A shader module and the values of its specialization constants, sorted by id.
+#[derive(PartialEq, Hash)]
+struct SpecializedModuleKey {
+    module: wgn::ShaderModuleId,
+    constants: Vec<(u32, u32)>,
 }
 
+// Ids compare their index and epoch, so the equality is total.
+impl Eq for SpecializedModuleKey {}
+
+#[derive(Debug)]
 pub struct Buffer {
     id: wgn::BufferId,
+    label: Option<String>,
 }
 
+#[derive(Debug)]
 pub struct Texture {
     id: wgn::TextureId,
+    label: Option<String>,
     owned: bool,
+    size: Extent3d,
+    array_size: u32,
+    /// Always one, as wgpu-native creates textures without a mip chain.
+    mip_level_count: u32,
+    dimension: TextureDimension,
+    format: TextureFormat,
 }
 
+#[derive(Debug)]
 pub struct TextureView {
     id: wgn::TextureViewId,
+    label: Option<String>,
     owned: bool,
 }
 
+#[derive(Debug)]
 pub struct Sampler {
     id: wgn::SamplerId,
+    label: Option<String>,
 }
 
 pub struct Surface {
     id: wgn::SurfaceId,
 }
 
+#[derive(Debug)]
 pub struct SwapChain {
     id: wgn::SwapChainId,
+    label: Option<String>,
+    format: TextureFormat,
+    width: u32,
+    height: u32,
 }
 
+#[derive(Debug)]
 pub struct BindGroupLayout {
     id: wgn::BindGroupLayoutId,
+    label: Option<String>,
 }
 
+#[derive(Debug)]
 pub struct BindGroup {
     id: wgn::BindGroupId,
+    label: Option<String>,
 }
 
 impl Drop for BindGroup {
@@ -121,30 +268,61 @@ impl Drop for BindGroup {
 
 pub struct ShaderModule {
     id: wgn::ShaderModuleId,
+    label: Option<String>,
+    /// Kept for reflection and specialization.
+    spv: Vec<u32>,
 }
 
+impl fmt::Debug for ShaderModule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ShaderModule")
+            .field("id", &self.id)
+            .field("label", &self.label)
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShaderStage {
+    Vertex,
+    Fragment,
+    Compute,
+}
+
+#[derive(Debug)]
 pub struct PipelineLayout {
     id: wgn::PipelineLayoutId,
+    label: Option<String>,
 }
 
+#[derive(Debug)]
 pub struct RenderPipeline {
     id: wgn::RenderPipelineId,
+    label: Option<String>,
+    has_fragment_stage: bool,
 }
 
+#[derive(Debug)]
 pub struct ComputePipeline {
     id: wgn::ComputePipelineId,
+    label: Option<String>,
 }
 
+#[derive(Debug)]
 pub struct CommandBuffer {
     id: wgn::CommandBufferId,
+    label: Option<String>,
 }
 
+#[derive(Debug)]
 pub struct CommandEncoder {
     id: wgn::CommandEncoderId,
+    label: Option<String>,
 }
 
 pub struct RenderPass<'a> {
     id: wgn::RenderPassId,
+    has_color_attachments: bool,
     _parent: &'a mut CommandEncoder,
 }
 
@@ -172,22 +350,157 @@ pub struct Binding<'a> {
     pub resource: BindingResource<'a>,
 }
 
+#[derive(Clone, Debug)]
+pub struct BufferDescriptor<'a> {
+    pub label: Option<&'a str>,
+    pub size: u32,
+    pub usage: BufferUsageFlags,
+}
+
+#[derive(Clone, Debug)]
+pub struct TextureDescriptor<'a> {
+    pub label: Option<&'a str>,
+    pub size: Extent3d,
+    pub array_size: u32,
+    pub dimension: TextureDimension,
+    pub format: TextureFormat,
+    pub usage: TextureUsageFlags,
+}
+
+#[derive(Clone, Debug)]
+pub struct SamplerDescriptor<'a> {
+    pub label: Option<&'a str>,
+    pub r_address_mode: AddressMode,
+    pub s_address_mode: AddressMode,
+    pub t_address_mode: AddressMode,
+    pub mag_filter: FilterMode,
+    pub min_filter: FilterMode,
+    pub mipmap_filter: FilterMode,
+    pub lod_min_clamp: f32,
+    pub lod_max_clamp: f32,
+    pub max_anisotropy: u32,
+    pub compare_function: CompareFunction,
+    pub border_color: BorderColor,
+}
+
+impl<'a> Default for SamplerDescriptor<'a> {
+    /// Nearest filtering of all mip levels, clamped to the edge.
+    fn default() -> Self {
+        SamplerDescriptor {
+            label: None,
+            r_address_mode: AddressMode::ClampToEdge,
+            s_address_mode: AddressMode::ClampToEdge,
+            t_address_mode: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Nearest,
+            mipmap_filter: FilterMode::Nearest,
+            lod_min_clamp: -100.0,
+            lod_max_clamp: 100.0,
+            max_anisotropy: 0,
+            compare_function: CompareFunction::Always,
+            border_color: BorderColor::TransparentBlack,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CommandEncoderDescriptor<'a> {
+    pub label: Option<&'a str>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ShaderModuleDescriptor<'a> {
+    pub label: Option<&'a str>,
+    /// SPIR-V words, as returned by `read_spirv`.
+    pub code: &'a [u32],
+}
+
+#[derive(Clone, Debug)]
+pub struct TextureViewDescriptor<'a> {
+    pub label: Option<&'a str>,
+    pub format: TextureFormat,
+    pub dimension: TextureViewDimension,
+    pub aspect: TextureAspectFlags,
+    pub base_mip_level: u32,
+    pub level_count: u32,
+    pub base_array_layer: u32,
+    pub array_count: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct SwapChainDescriptor<'a> {
+    pub label: Option<&'a str>,
+    pub usage: TextureUsageFlags,
+    pub format: TextureFormat,
+    pub width: u32,
+    pub height: u32,
+}
+
 pub struct BindGroupLayoutDescriptor<'a> {
+    pub label: Option<&'a str>,
     pub bindings: &'a [BindGroupLayoutBinding],
 }
 
 pub struct BindGroupDescriptor<'a> {
+    pub label: Option<&'a str>,
     pub layout: &'a BindGroupLayout,
     pub bindings: &'a [Binding<'a>],
 }
 
 pub struct PipelineLayoutDescriptor<'a> {
+    pub label: Option<&'a str>,
     pub bind_group_layouts: &'a [&'a BindGroupLayout],
 }
 
+#[derive(Clone)]
 pub struct PipelineStageDescriptor<'a> {
     pub module: &'a ShaderModule,
     pub entry_point: &'a str,
+    /// Values overriding the defaults of specialization constants in the module.
+    ///
+    /// A stage with specialization constants uses a patched copy of its module, see
+    /// `specialize_spirv`.
+    pub specialization: &'a [SpecializationConstant],
+}
+
+/// Value of a specialization constant, which has to match the type declared in the shader.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpecializationValue {
+    Bool(bool),
+    Int(i32),
+    Uint(u32),
+    Float(f32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpecializationConstant {
+    /// The `constant_id` given to the constant in the shader.
+    pub id: u32,
+    pub value: SpecializationValue,
+}
+
+impl SpecializationValue {
+    pub(crate) fn to_bits(self) -> u32 {
+        match self {
+            SpecializationValue::Bool(value) => value as u32,
+            SpecializationValue::Int(value) => value as u32,
+            SpecializationValue::Uint(value) => value,
+            SpecializationValue::Float(value) => value.to_bits(),
+        }
+    }
+}
+
+impl<'a> PipelineStageDescriptor<'a> {
+    fn sorted_specialization(&self) -> Vec<SpecializationConstant> {
+        let mut constants = self.specialization.to_vec();
+        constants.sort_by_key(|constant| constant.id);
+        assert!(
+            constants.windows(2).all(|pair| pair[0].id != pair[1].id),
+            "Specialization constants of entry point {:?} are set more than once",
+            self.entry_point
+        );
+        constants
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -198,9 +511,12 @@ pub struct VertexBufferDescriptor<'a> {
 }
 
 pub struct RenderPipelineDescriptor<'a> {
+    pub label: Option<&'a str>,
     pub layout: &'a PipelineLayout,
     pub vertex_stage: PipelineStageDescriptor<'a>,
-    pub fragment_stage: PipelineStageDescriptor<'a>,
+    /// Fragment shader, which depth-only pipelines leave out.
+    /// Without one, the pipeline can have no color states.
+    pub fragment_stage: Option<PipelineStageDescriptor<'a>>,
     pub rasterization_state: RasterizationStateDescriptor,
     pub primitive_topology: PrimitiveTopology,
     pub color_states: &'a [ColorStateDescriptor],
@@ -211,6 +527,7 @@ pub struct RenderPipelineDescriptor<'a> {
 }
 
 pub struct ComputePipelineDescriptor<'a> {
+    pub label: Option<&'a str>,
     pub layout: &'a PipelineLayout,
     pub compute_stage: PipelineStageDescriptor<'a>,
 }
@@ -279,7 +596,10 @@ where
 
     pub fn finish(self) -> Buffer {
         wgn::wgpu_buffer_unmap(self.id);
-        Buffer { id: self.id }
+        Buffer {
+            id: self.id,
+            label: None,
+        }
     }
 }
 
@@ -296,6 +616,7 @@ impl Instance {
         }
     }
 
+    #[cfg(feature = "window-winit")]
     pub fn create_surface(&self, window: &winit::Window) -> Surface {
         Surface {
             id: wgn::wgpu_instance_create_surface_from_winit(self.id, window),
@@ -315,6 +636,9 @@ impl Adapter {
         Device {
             id: wgn::wgpu_adapter_create_device(self.id, desc),
             temp: Temp::default(),
+            specialized_modules: Mutex::new(HashMap::new()),
+            empty_fragment_module: Mutex::new(None),
+            objects: None,
         }
     }
 }
@@ -325,16 +649,77 @@ impl Device {
         wgn::wgpu_device_poll(self.id, force_wait);
     }
 
-    pub fn create_shader_module(&self, spv: &[u8]) -> ShaderModule {
+    /// Create a shader module from SPIR-V words, as returned by `read_spirv`.
+    pub fn create_shader_module(&self, spv: &[u32]) -> ShaderModule {
+        self.create_shader_module_from_descriptor(&ShaderModuleDescriptor {
+            label: None,
+            code: spv,
+        })
+    }
+
+    pub fn create_shader_module_from_descriptor(
+        &self,
+        desc: &ShaderModuleDescriptor,
+    ) -> ShaderModule {
+        self.try_create_shader_module(desc).unwrap_or_else(|err| {
+            panic!(
+                "Shader module {} is not a valid SPIR-V module: {}",
+                DisplayLabel(desc.label),
+                err
+            )
+        })
+    }
+
+    /// Create a shader module, checking the SPIR-V header with `validate_spirv` first.
+    pub fn try_create_shader_module(
+        &self,
+        desc: &ShaderModuleDescriptor,
+    ) -> Result<ShaderModule, SpirvError> {
+        validate_spirv(desc.code)?;
+        Ok(ShaderModule {
+            id: self.create_native_shader_module(desc.code),
+            label: desc.label.map(String::from),
+            spv: desc.code.to_vec(),
+        })
+    }
+
+    fn create_native_shader_module(&self, spv: &[u32]) -> wgn::ShaderModuleId {
         let desc = wgn::ShaderModuleDescriptor {
             code: wgn::ByteArray {
-                bytes: spv.as_ptr(),
-                length: spv.len(),
+                bytes: spv.as_ptr() as *const u8,
+                length: spv.len() * 4,
             },
         };
-        ShaderModule {
-            id: wgn::wgpu_device_create_shader_module(self.id, &desc),
+        wgn::wgpu_device_create_shader_module(self.id, &desc)
+    }
+
+    /// The module of a pipeline stage, or a copy of it with the specialization
+    /// constants of the stage patched in.
+    fn stage_module(&self, stage: &PipelineStageDescriptor) -> wgn::ShaderModuleId {
+        if stage.specialization.is_empty() {
+            return stage.module.id;
         }
+        let constants = stage.sorted_specialization();
+        let key = SpecializedModuleKey {
+            module: stage.module.id,
+            constants: constants
+                .iter()
+                .map(|constant| (constant.id, constant.value.to_bits()))
+                .collect(),
+        };
+        let mut modules = self.specialized_modules.lock().unwrap();
+        *modules.entry(key).or_insert_with(|| {
+            self.create_native_shader_module(&specialize_spirv(&stage.module.spv, &constants))
+        })
+    }
+
+    /// A fragment module writing no outputs, for depth-only pipelines, since
+    /// wgpu-native cannot create a render pipeline without a fragment stage.
+    fn empty_fragment_module(&self) -> wgn::ShaderModuleId {
+        let mut module = self.empty_fragment_module.lock().unwrap();
+        *module.get_or_insert_with(|| {
+            self.create_native_shader_module(&spirv::EMPTY_FRAGMENT_SHADER)
+        })
     }
 
     pub fn get_queue(&mut self) -> Queue {
@@ -346,7 +731,11 @@ impl Device {
 
     pub fn create_command_encoder(&self, desc: &CommandEncoderDescriptor) -> CommandEncoder {
         CommandEncoder {
-            id: wgn::wgpu_device_create_command_encoder(self.id, desc),
+            id: wgn::wgpu_device_create_command_encoder(
+                self.id,
+                &wgn::CommandEncoderDescriptor { todo: 0 },
+            ),
+            label: desc.label.map(String::from),
         }
     }
 
@@ -383,6 +772,7 @@ impl Device {
                     bindings_length: bindings.len(),
                 },
             ),
+            label: desc.label.map(String::from),
         }
     }
 
@@ -395,6 +785,7 @@ impl Device {
                     bindings_length: desc.bindings.len(),
                 },
             ),
+            label: desc.label.map(String::from),
         }
     }
 
@@ -413,12 +804,27 @@ impl Device {
                     bind_group_layouts_length: temp_layouts.len(),
                 },
             ),
+            label: desc.label.map(String::from),
         }
     }
 
     pub fn create_render_pipeline(&self, desc: &RenderPipelineDescriptor) -> RenderPipeline {
+        assert!(
+            desc.fragment_stage.is_some() || desc.color_states.is_empty(),
+            "Render pipeline {} has color states but no fragment stage",
+            DisplayLabel(desc.label)
+        );
         let vertex_entry_point = CString::new(desc.vertex_stage.entry_point).unwrap();
-        let fragment_entry_point = CString::new(desc.fragment_stage.entry_point).unwrap();
+        let (fragment_module, fragment_entry_point) = match desc.fragment_stage {
+            Some(ref stage) => (
+                self.stage_module(stage),
+                CString::new(stage.entry_point).unwrap(),
+            ),
+            None => (
+                self.empty_fragment_module(),
+                CString::new(spirv::EMPTY_FRAGMENT_ENTRY_POINT).unwrap(),
+            ),
+        };
 
         let temp_color_states = desc.color_states.to_vec();
         let temp_vertex_buffers = desc
@@ -438,11 +844,11 @@ impl Device {
                 &wgn::RenderPipelineDescriptor {
                     layout: desc.layout.id,
                     vertex_stage: wgn::PipelineStageDescriptor {
-                        module: desc.vertex_stage.module.id,
+                        module: self.stage_module(&desc.vertex_stage),
                         entry_point: vertex_entry_point.as_ptr(),
                     },
                     fragment_stage: wgn::PipelineStageDescriptor {
-                        module: desc.fragment_stage.module.id,
+                        module: fragment_module,
                         entry_point: fragment_entry_point.as_ptr(),
                     },
                     rasterization_state: desc.rasterization_state.clone(),
@@ -461,6 +867,8 @@ impl Device {
                     sample_count: desc.sample_count,
                 },
             ),
+            label: desc.label.map(String::from),
+            has_fragment_stage: desc.fragment_stage.is_some(),
         }
     }
 
@@ -473,17 +881,25 @@ impl Device {
                 &wgn::ComputePipelineDescriptor {
                     layout: desc.layout.id,
                     compute_stage: wgn::PipelineStageDescriptor {
-                        module: desc.compute_stage.module.id,
+                        module: self.stage_module(&desc.compute_stage),
                         entry_point: entry_point.as_ptr(),
                     },
                 },
             ),
+            label: desc.label.map(String::from),
         }
     }
 
     pub fn create_buffer(&self, desc: &BufferDescriptor) -> Buffer {
         Buffer {
-            id: wgn::wgpu_device_create_buffer(self.id, desc),
+            id: wgn::wgpu_device_create_buffer(
+                self.id,
+                &wgn::BufferDescriptor {
+                    size: desc.size,
+                    usage: desc.usage,
+                },
+            ),
+            label: desc.label.map(String::from),
         }
     }
 
@@ -498,7 +914,7 @@ impl Device {
         let type_size = std::mem::size_of::<T>() as u32;
         assert_ne!(type_size, 0);
 
-        let desc = BufferDescriptor {
+        let desc = wgn::BufferDescriptor {
             size: (type_size * count as u32).max(1),
             usage,
         };
@@ -513,20 +929,64 @@ impl Device {
 
     pub fn create_texture(&self, desc: &TextureDescriptor) -> Texture {
         Texture {
-            id: wgn::wgpu_device_create_texture(self.id, desc),
+            id: wgn::wgpu_device_create_texture(
+                self.id,
+                &wgn::TextureDescriptor {
+                    size: desc.size,
+                    array_size: desc.array_size,
+                    dimension: desc.dimension,
+                    format: desc.format,
+                    usage: desc.usage,
+                },
+            ),
+            label: desc.label.map(String::from),
             owned: true,
+            size: desc.size,
+            array_size: desc.array_size,
+            mip_level_count: 1,
+            dimension: desc.dimension,
+            format: desc.format,
         }
     }
 
     pub fn create_sampler(&self, desc: &SamplerDescriptor) -> Sampler {
         Sampler {
-            id: wgn::wgpu_device_create_sampler(self.id, desc),
+            id: wgn::wgpu_device_create_sampler(
+                self.id,
+                &wgn::SamplerDescriptor {
+                    r_address_mode: desc.r_address_mode,
+                    s_address_mode: desc.s_address_mode,
+                    t_address_mode: desc.t_address_mode,
+                    mag_filter: desc.mag_filter,
+                    min_filter: desc.min_filter,
+                    mipmap_filter: desc.mipmap_filter,
+                    lod_min_clamp: desc.lod_min_clamp,
+                    lod_max_clamp: desc.lod_max_clamp,
+                    max_anisotropy: desc.max_anisotropy,
+                    compare_function: desc.compare_function,
+                    border_color: desc.border_color,
+                },
+            ),
+            label: desc.label.map(String::from),
         }
     }
 
     pub fn create_swap_chain(&self, surface: &Surface, desc: &SwapChainDescriptor) -> SwapChain {
         SwapChain {
-            id: wgn::wgpu_device_create_swap_chain(self.id, surface.id, desc),
+            id: wgn::wgpu_device_create_swap_chain(
+                self.id,
+                surface.id,
+                &wgn::SwapChainDescriptor {
+                    usage: desc.usage,
+                    format: desc.format,
+                    width: desc.width,
+                    height: desc.height,
+                },
+            ),
+            label: desc.label.map(String::from),
+            format: desc.format,
+            width: desc.width,
+            height: desc.height,
         }
     }
 }
@@ -684,9 +1144,41 @@ impl Drop for Buffer {
 }
 
 impl Texture {
+    pub fn format(&self) -> TextureFormat {
+        self.format
+    }
+
+    pub fn mip_level_count(&self) -> u32 {
+        self.mip_level_count
+    }
+
+    /// Size of the given mip level, clamped to at least one texel per axis.
+    pub fn level_size(&self, level: u32) -> Extent3d {
+        Extent3d {
+            width: (self.size.width >> level).max(1),
+            height: (self.size.height >> level).max(1),
+            depth: match self.dimension {
+                TextureDimension::D3 => (self.size.depth >> level).max(1),
+                _ => 1,
+            },
+        }
+    }
+
     pub fn create_view(&self, desc: &TextureViewDescriptor) -> TextureView {
         TextureView {
-            id: wgn::wgpu_texture_create_view(self.id, desc),
+            id: wgn::wgpu_texture_create_view(
+                self.id,
+                &wgn::TextureViewDescriptor {
+                    format: desc.format,
+                    dimension: desc.dimension,
+                    aspect: desc.aspect,
+                    base_mip_level: desc.base_mip_level,
+                    level_count: desc.level_count,
+                    base_array_layer: desc.base_array_layer,
+                    array_count: desc.array_count,
+                },
+            ),
+            label: desc.label.map(String::from),
             owned: true,
         }
     }
@@ -694,6 +1186,7 @@ impl Texture {
     pub fn create_default_view(&self) -> TextureView {
         TextureView {
             id: wgn::wgpu_texture_create_default_view(self.id),
+            label: None,
             owned: true,
         }
     }
@@ -719,6 +1212,7 @@ impl CommandEncoder {
     pub fn finish(self) -> CommandBuffer {
         CommandBuffer {
             id: wgn::wgpu_command_encoder_finish(self.id),
+            label: self.label,
         }
     }
 
@@ -758,6 +1252,7 @@ impl CommandEncoder {
                         .unwrap_or(ptr::null()),
                 },
             ),
+            has_color_attachments: !desc.color_attachments.is_empty(),
             _parent: self,
         }
     }
@@ -842,6 +1337,11 @@ impl<'a> RenderPass<'a> {
     }
 
     pub fn set_pipeline(&mut self, pipeline: &RenderPipeline) {
+        assert!(
+            pipeline.has_fragment_stage || !self.has_color_attachments,
+            "Render pipeline {} has no fragment stage but the pass has color attachments",
+            DisplayLabel::of(&pipeline.label)
+        );
         wgn::wgpu_render_pass_set_pipeline(self.id, pipeline.id);
     }
 
@@ -953,10 +1453,21 @@ impl SwapChain {
         SwapChainOutput {
             texture: Texture {
                 id: output.texture_id,
+                label: None,
                 owned: false,
+                size: Extent3d {
+                    width: self.width,
+                    height: self.height,
+                    depth: 1,
+                },
+                array_size: 1,
+                mip_level_count: 1,
+                dimension: TextureDimension::D2,
+                format: self.format,
             },
             view: TextureView {
                 id: output.view_id,
+                label: None,
                 owned: false,
             },
             swap_chain_id: &self.id,
@@ -0,0 +1,157 @@
+use super::{LoadError, Reader, TextureData};
+use crate::{Extent3d, TextureDimension, TextureFormat};
+
+const MAGIC: &[u8; 4] = b"DDS ";
+const HEADER_SIZE: u32 = 124;
+
+const DDSD_DEPTH: u32 = 0x80_0000;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+const DDS_RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+const D3D10_RESOURCE_DIMENSION_TEXTURE1D: u32 = 2;
+const D3D10_RESOURCE_DIMENSION_TEXTURE3D: u32 = 4;
+
+fn fourcc(code: &[u8; 4]) -> u32 {
+    u32::from(code[0])
+        | u32::from(code[1]) << 8
+        | u32::from(code[2]) << 16
+        | u32::from(code[3]) << 24
+}
+
+fn format_from_dxgi(dxgi_format: u32) -> Option<TextureFormat> {
+    use crate::TextureFormat::*;
+    Some(match dxgi_format {
+        2 => Rgba32Float,
+        3 => Rgba32Uint,
+        4 => Rgba32Sint,
+        10 => Rgba16Float,
+        16 => Rg32Float,
+        24 => Rgb10a2Unorm,
+        26 => Rg11b10Float,
+        28 => Rgba8Unorm,
+        29 => Rgba8UnormSrgb,
+        30 => Rgba8Uint,
+        31 => Rgba8Snorm,
+        32 => Rgba8Sint,
+        34 => Rg16Float,
+        41 => R32Float,
+        42 => R32Uint,
+        43 => R32Sint,
+        49 => Rg8Unorm,
+        54 => R16Float,
+        61 => R8Unorm,
+        87 => Bgra8Unorm,
+        91 => Bgra8UnormSrgb,
+        _ => return None,
+    })
+}
+
+/// Parse a DDS file, including all mip levels, array layers and cube faces.
+///
+/// Both legacy headers and the DX10 extension header are understood. Partial cube
+/// maps are not supported.
+pub fn parse_dds(bytes: &[u8]) -> Result<TextureData, LoadError> {
+    if !bytes.starts_with(MAGIC) {
+        return Err(LoadError::InvalidMagic);
+    }
+    let mut reader = Reader::new(bytes, MAGIC.len());
+    if reader.u32()? != HEADER_SIZE {
+        return Err(LoadError::InvalidHeader);
+    }
+
+    let flags = reader.u32()?;
+    let height = reader.u32()?;
+    let width = reader.u32()?;
+    let _pitch_or_linear_size = reader.u32()?;
+    let depth = reader.u32()?;
+    let mip_map_count = reader.u32()?;
+    reader.bytes(11 * 4)?;
+
+    let _pixel_format_size = reader.u32()?;
+    let pixel_format_flags = reader.u32()?;
+    let pixel_format_fourcc = reader.u32()?;
+    let rgb_bit_count = reader.u32()?;
+    let red_mask = reader.u32()?;
+    let _green_mask = reader.u32()?;
+    let _blue_mask = reader.u32()?;
+    let _alpha_mask = reader.u32()?;
+
+    let _caps = reader.u32()?;
+    let caps2 = reader.u32()?;
+    reader.bytes(3 * 4)?;
+
+    let is_3d = flags & DDSD_DEPTH != 0 && depth > 1;
+    let (format, dimension, array_size, cube) =
+        if pixel_format_flags & DDPF_FOURCC != 0 && pixel_format_fourcc == fourcc(b"DX10") {
+            let dxgi_format = reader.u32()?;
+            let resource_dimension = reader.u32()?;
+            let misc_flag = reader.u32()?;
+            let array_size = reader.u32()?;
+            let _misc_flags2 = reader.u32()?;
+
+            let format =
+                format_from_dxgi(dxgi_format).ok_or(LoadError::UnsupportedFormat(dxgi_format))?;
+            let dimension = match resource_dimension {
+                D3D10_RESOURCE_DIMENSION_TEXTURE1D => TextureDimension::D1,
+                D3D10_RESOURCE_DIMENSION_TEXTURE3D => TextureDimension::D3,
+                _ => TextureDimension::D2,
+            };
+            let cube = misc_flag & DDS_RESOURCE_MISC_TEXTURECUBE != 0;
+            let layers = array_size
+                .max(1)
+                .checked_mul(if cube { 6 } else { 1 })
+                .ok_or(LoadError::InvalidHeader)?;
+            (format, dimension, layers, cube)
+        } else {
+            let format = if pixel_format_flags & DDPF_FOURCC != 0 {
+                return Err(LoadError::UnsupportedFormat(pixel_format_fourcc));
+            } else if pixel_format_flags & DDPF_RGB != 0 && rgb_bit_count == 32 {
+                match red_mask {
+                    0x0000_00FF => TextureFormat::Rgba8Unorm,
+                    0x00FF_0000 => TextureFormat::Bgra8Unorm,
+                    _ => return Err(LoadError::UnsupportedFormat(red_mask)),
+                }
+            } else {
+                return Err(LoadError::UnsupportedFormat(pixel_format_flags));
+            };
+            let dimension = if is_3d {
+                TextureDimension::D3
+            } else {
+                TextureDimension::D2
+            };
+            let cube = caps2 & DDSCAPS2_CUBEMAP != 0;
+            (format, dimension, if cube { 6 } else { 1 }, cube)
+        };
+
+    if width == 0 || height == 0 || (dimension == TextureDimension::D3 && array_size > 1) {
+        return Err(LoadError::InvalidHeader);
+    }
+
+    let mut data = TextureData::new(
+        format,
+        dimension,
+        Extent3d {
+            width,
+            height,
+            depth: if dimension == TextureDimension::D3 {
+                depth.max(1)
+            } else {
+                1
+            },
+        },
+        array_size,
+        cube,
+        mip_map_count.max(1),
+    )?;
+
+    // Images are stored layer by layer, each with its complete mip chain.
+    for _ in 0 .. data.array_size {
+        for level in 0 .. data.mip_level_count {
+            let image = reader.bytes(data.level_byte_size(level)?)?;
+            data.images.push(image.to_vec());
+        }
+    }
+
+    Ok(data)
+}
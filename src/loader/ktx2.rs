@@ -0,0 +1,122 @@
+use super::{LoadError, Reader, TextureData};
+use crate::{Extent3d, TextureDimension, TextureFormat};
+
+use std::convert::TryFrom;
+
+const MAGIC: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+
+fn format_from_vk(vk_format: u32) -> Option<TextureFormat> {
+    use crate::TextureFormat::*;
+    Some(match vk_format {
+        9 => R8Unorm,
+        10 => R8Snorm,
+        13 => R8Uint,
+        14 => R8Sint,
+        15 => R8UnormSrgb,
+        16 => Rg8Unorm,
+        17 => Rg8Snorm,
+        20 => Rg8Uint,
+        21 => Rg8Sint,
+        22 => Rg8UnormSrgb,
+        37 => Rgba8Unorm,
+        38 => Rgba8Snorm,
+        41 => Rgba8Uint,
+        42 => Rgba8Sint,
+        43 => Rgba8UnormSrgb,
+        44 => Bgra8Unorm,
+        50 => Bgra8UnormSrgb,
+        64 => Rgb10a2Unorm,
+        76 => R16Float,
+        83 => Rg16Float,
+        97 => Rgba16Float,
+        98 => R32Uint,
+        99 => R32Sint,
+        100 => R32Float,
+        103 => Rg32Float,
+        107 => Rgba32Uint,
+        108 => Rgba32Sint,
+        109 => Rgba32Float,
+        122 => Rg11b10Float,
+        _ => return None,
+    })
+}
+
+/// Parse a KTX2 file, including all mip levels, array layers and cube faces.
+pub fn parse_ktx2(bytes: &[u8]) -> Result<TextureData, LoadError> {
+    if !bytes.starts_with(&MAGIC) {
+        return Err(LoadError::InvalidMagic);
+    }
+    let mut reader = Reader::new(bytes, MAGIC.len());
+
+    let vk_format = reader.u32()?;
+    let _type_size = reader.u32()?;
+    let width = reader.u32()?;
+    let height = reader.u32()?;
+    let depth = reader.u32()?;
+    let layer_count = reader.u32()?;
+    let face_count = reader.u32()?;
+    let level_count = reader.u32()?;
+    let supercompression = reader.u32()?;
+
+    let format = format_from_vk(vk_format).ok_or(LoadError::UnsupportedFormat(vk_format))?;
+    if supercompression != 0 {
+        return Err(LoadError::Supercompressed(supercompression));
+    }
+    if width == 0 || (face_count != 1 && face_count != 6) || (depth > 1 && layer_count > 1) {
+        return Err(LoadError::InvalidHeader);
+    }
+    let dimension = if depth > 1 {
+        TextureDimension::D3
+    } else if height > 0 {
+        TextureDimension::D2
+    } else {
+        TextureDimension::D1
+    };
+
+    let mut data = TextureData::new(
+        format,
+        dimension,
+        Extent3d {
+            width,
+            height: height.max(1),
+            depth: depth.max(1),
+        },
+        layer_count
+            .max(1)
+            .checked_mul(face_count)
+            .ok_or(LoadError::InvalidHeader)?,
+        face_count == 6,
+        level_count.max(1),
+    )?;
+
+    // Skip the data format descriptor, key/value data and supercompression global data.
+    reader.bytes(4 * 4 + 8 * 2)?;
+
+    let mut levels = Vec::with_capacity(data.mip_level_count as usize);
+    for _ in 0 .. data.mip_level_count {
+        let offset = usize::try_from(reader.u64()?).map_err(|_| LoadError::Truncated)?;
+        let length = usize::try_from(reader.u64()?).map_err(|_| LoadError::Truncated)?;
+        let _uncompressed_length = reader.u64()?;
+        let end = offset.checked_add(length).ok_or(LoadError::Truncated)?;
+        levels.push(bytes.get(offset .. end).ok_or(LoadError::Truncated)?);
+    }
+
+    // Levels store every layer and face back to back, while `TextureData` is layer major.
+    for layer in 0 .. data.array_size as usize {
+        for (level, level_bytes) in levels.iter().enumerate() {
+            let image_size = data.level_byte_size(level as u32)?;
+            let start = layer
+                .checked_mul(image_size)
+                .ok_or(LoadError::InvalidHeader)?;
+            let image = level_bytes
+                .get(start ..)
+                .and_then(|rest| rest.get(.. image_size))
+                .ok_or(LoadError::Truncated)?;
+            data.images.push(image.to_vec());
+        }
+    }
+
+    Ok(data)
+}
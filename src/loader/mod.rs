@@ -0,0 +1,230 @@
+//! Parsing of KTX2 and DDS texture containers.
+
+mod dds;
+mod ktx2;
+
+pub use self::dds::parse_dds;
+pub use self::ktx2::parse_ktx2;
+
+use crate::readback::align_row_pitch;
+use crate::{
+    BufferCopyView,
+    BufferUsageFlags,
+    CommandEncoder,
+    Device,
+    Extent3d,
+    Origin3d,
+    Texture,
+    TextureCopyView,
+    TextureDescriptor,
+    TextureDimension,
+    TextureFormat,
+    TextureFormatExt,
+    TextureUsageFlags,
+};
+
+use std::convert::TryFrom;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LoadError {
+    /// The data does not start with the magic number of the container.
+    InvalidMagic,
+    /// The header describes an image that this crate cannot represent.
+    InvalidHeader,
+    /// The pixel format has no matching `TextureFormat`.
+    UnsupportedFormat(u32),
+    /// KTX2 supercompression is not supported.
+    Supercompressed(u32),
+    /// The data has a mip chain of the given length, but wgpu-native creates textures
+    /// with a single mip level.
+    MipChain(u32),
+    /// The data ends before all images described by the header.
+    Truncated,
+}
+
+/// Mip levels beyond this cannot exist for any texture size that fits in a `u32`.
+const MAX_MIP_LEVEL_COUNT: u32 = 32;
+
+/// Texture contents read from a container file, with the full mip chain of every layer.
+#[derive(Clone, Debug)]
+pub struct TextureData {
+    pub format: TextureFormat,
+    pub dimension: TextureDimension,
+    /// Size of the first mip level.
+    pub size: Extent3d,
+    /// Number of array layers. Cube maps have six consecutive layers per cube,
+    /// ordered +X, -X, +Y, -Y, +Z, -Z.
+    pub array_size: u32,
+    pub cube: bool,
+    pub mip_level_count: u32,
+    /// Images ordered by array layer, then by mip level.
+    images: Vec<Vec<u8>>,
+}
+
+impl TextureData {
+    fn new(
+        format: TextureFormat,
+        dimension: TextureDimension,
+        size: Extent3d,
+        array_size: u32,
+        cube: bool,
+        mip_level_count: u32,
+    ) -> Result<Self, LoadError> {
+        if mip_level_count > MAX_MIP_LEVEL_COUNT {
+            return Err(LoadError::InvalidHeader);
+        }
+        // Images are only allocated as they are read, so that a header claiming a huge
+        // number of them fails on the missing data instead of on the allocation.
+        Ok(TextureData {
+            format,
+            dimension,
+            size,
+            array_size,
+            cube,
+            mip_level_count,
+            images: Vec::new(),
+        })
+    }
+
+    /// Size of the given mip level, clamped to at least one texel per axis.
+    pub fn level_size(&self, level: u32) -> Extent3d {
+        Extent3d {
+            width: (self.size.width >> level).max(1),
+            height: (self.size.height >> level).max(1),
+            depth: (self.size.depth >> level).max(1),
+        }
+    }
+
+    /// Drop every mip level but the first, keeping all array layers.
+    pub fn truncate_mip_chain(&mut self) {
+        let level_count = self.mip_level_count as usize;
+        self.images = self.images.drain(..).step_by(level_count).collect();
+        self.mip_level_count = 1;
+    }
+
+    /// Tightly packed contents of one mip level of one array layer.
+    pub fn image(&self, layer: u32, level: u32) -> &[u8] {
+        &self.images[(layer * self.mip_level_count + level) as usize]
+    }
+
+    /// Size in bytes of one image of the given level, or `InvalidHeader` if it does not
+    /// fit in memory.
+    fn level_byte_size(&self, level: u32) -> Result<usize, LoadError> {
+        let size = self.level_size(level);
+        u64::from(size.width)
+            .checked_mul(u64::from(size.height))
+            .and_then(|texels| texels.checked_mul(u64::from(size.depth)))
+            .and_then(|texels| texels.checked_mul(u64::from(self.format.info().block_size)))
+            .and_then(|bytes| usize::try_from(bytes).ok())
+            .ok_or(LoadError::InvalidHeader)
+    }
+}
+
+/// Cursor over the little endian fields of a container header.
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8], offset: usize) -> Self {
+        Reader { data, offset }
+    }
+
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8], LoadError> {
+        let end = self
+            .offset
+            .checked_add(count)
+            .ok_or(LoadError::Truncated)?;
+        let bytes = self
+            .data
+            .get(self.offset .. end)
+            .ok_or(LoadError::Truncated)?;
+        self.offset += count;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, LoadError> {
+        let b = self.bytes(4)?;
+        Ok(u32::from(b[0]) | u32::from(b[1]) << 8 | u32::from(b[2]) << 16 | u32::from(b[3]) << 24)
+    }
+
+    fn u64(&mut self) -> Result<u64, LoadError> {
+        let low = u64::from(self.u32()?);
+        let high = u64::from(self.u32()?);
+        Ok(low | high << 32)
+    }
+}
+
+impl Device {
+    /// Create a texture holding every layer of `data`, recording the uploads into `encoder`.
+    ///
+    /// wgpu-native creates textures with a single mip level, so data with a longer mip
+    /// chain fails with `LoadError::MipChain` unless it is cut with `truncate_mip_chain`
+    /// first. Cube maps are created as 2D textures with six layers per cube.
+    /// `TRANSFER_DST` is always added to the requested `usage`.
+    pub fn create_texture_from_data(
+        &self,
+        encoder: &mut CommandEncoder,
+        data: &TextureData,
+        usage: TextureUsageFlags,
+    ) -> Result<Texture, LoadError> {
+        if data.mip_level_count > 1 {
+            return Err(LoadError::MipChain(data.mip_level_count));
+        }
+        let texture = self.create_texture(&TextureDescriptor {
+            label: None,
+            size: data.size,
+            array_size: data.array_size,
+            dimension: data.dimension,
+            format: data.format,
+            usage: usage | TextureUsageFlags::TRANSFER_DST,
+        });
+
+        // Every layer gets its own region of a single staging buffer, with rows padded
+        // to the copy alignment.
+        let size = data.level_size(0);
+        let row_size = data.format.info().block_size * size.width;
+        let rows = size.height * size.depth;
+        let row_pitch = align_row_pitch(row_size);
+        let layer_size = row_pitch * rows;
+
+        let staging = self.create_buffer_mapped::<u8>(
+            (layer_size * data.array_size) as usize,
+            BufferUsageFlags::TRANSFER_SRC,
+        );
+        for layer in 0 .. data.array_size {
+            let src_rows = data.image(layer, 0).chunks(row_size as usize);
+            let dst_rows =
+                staging.data[(layer * layer_size) as usize ..].chunks_mut(row_pitch as usize);
+            for (dst, src) in dst_rows.zip(src_rows) {
+                dst[.. row_size as usize].copy_from_slice(src);
+            }
+        }
+        let staging_buffer = staging.finish();
+
+        for layer in 0 .. data.array_size {
+            encoder.copy_buffer_to_texture(
+                BufferCopyView {
+                    buffer: &staging_buffer,
+                    offset: layer * layer_size,
+                    row_pitch,
+                    image_height: size.height,
+                },
+                TextureCopyView {
+                    texture: &texture,
+                    level: 0,
+                    slice: layer,
+                    origin: Origin3d {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 0.0,
+                    },
+                },
+                size,
+            );
+        }
+
+        Ok(texture)
+    }
+}
@@ -0,0 +1,380 @@
+use crate::{
+    BindGroup,
+    BindGroupDescriptor,
+    BindGroupLayout,
+    BindGroupLayoutDescriptor,
+    BindingResource,
+    BlendDescriptor,
+    ComputePipeline,
+    ComputePipelineDescriptor,
+    Device,
+    PipelineLayout,
+    PipelineLayoutDescriptor,
+    PipelineStageDescriptor,
+    RenderPipeline,
+    RenderPipelineDescriptor,
+    Sampler,
+    SamplerDescriptor,
+    StencilStateFaceDescriptor,
+};
+
+use std::collections::hash_map::{DefaultHasher, HashMap};
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::sync::{Arc, Mutex, Weak};
+
+/// Number of creations through the object cache of a device that returned an
+/// existing object (hits) or created a new one (misses).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ObjectCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Objects of one type, by the hash of the key of their descriptor.
+///
+/// Keys are compared on lookup, so that a hash collision cannot return an object
+/// created from another descriptor.
+struct Objects<K, T> {
+    entries: HashMap<u64, Vec<(K, Weak<T>)>>,
+    len: usize,
+    /// Number of entries at which the dropped objects are cleaned up.
+    purge_len: usize,
+}
+
+impl<K, T> Default for Objects<K, T> {
+    fn default() -> Self {
+        Objects {
+            entries: HashMap::new(),
+            len: 0,
+            purge_len: 16,
+        }
+    }
+}
+
+fn hash_key<K: Hash>(key: &K) -> u64 {
+    let mut state = DefaultHasher::new();
+    key.hash(&mut state);
+    state.finish()
+}
+
+impl<K: Hash + PartialEq, T> Objects<K, T> {
+    fn get(&self, key: &K) -> Option<Arc<T>> {
+        self.entries
+            .get(&hash_key(key))?
+            .iter()
+            .find(|(other, _)| other == key)
+            .and_then(|(_, object)| object.upgrade())
+    }
+
+    fn insert(&mut self, key: K, object: &Arc<T>) {
+        let object = Arc::downgrade(object);
+        let entries = self.entries.entry(hash_key(&key)).or_default();
+        match entries.iter_mut().find(|(other, _)| *other == key) {
+            Some(entry) => entry.1 = object,
+            None => {
+                entries.push((key, object));
+                self.len += 1;
+            }
+        }
+        if self.len >= self.purge_len {
+            for entries in self.entries.values_mut() {
+                entries.retain(|(_, object)| object.upgrade().is_some());
+            }
+            self.entries.retain(|_, entries| !entries.is_empty());
+            self.len = self.entries.values().map(Vec::len).sum();
+            self.purge_len = 2 * self.len.max(8);
+        }
+    }
+}
+
+/// Key of a pipeline stage, with the specialization constants sorted by id.
+#[derive(PartialEq, Hash)]
+struct StageKey {
+    module: wgn::ShaderModuleId,
+    entry_point: String,
+    specialization: Vec<(u32, u32)>,
+}
+
+impl StageKey {
+    fn new(stage: &PipelineStageDescriptor) -> Self {
+        StageKey {
+            module: stage.module.id,
+            entry_point: stage.entry_point.to_string(),
+            specialization: stage
+                .sorted_specialization()
+                .into_iter()
+                .map(|constant| (constant.id, constant.value.to_bits()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(PartialEq, Hash)]
+enum BindingKey {
+    Buffer(wgn::BufferId, Range<u32>),
+    Sampler(wgn::SamplerId),
+    TextureView(wgn::TextureViewId),
+}
+
+#[derive(PartialEq, Hash)]
+struct BindGroupKey {
+    layout: wgn::BindGroupLayoutId,
+    bindings: Vec<(u32, BindingKey)>,
+}
+
+/// Key of a render pipeline, with the fixed-function state flattened into words.
+#[derive(PartialEq, Hash)]
+struct RenderPipelineKey {
+    layout: wgn::PipelineLayoutId,
+    vertex_stage: StageKey,
+    fragment_stage: Option<StageKey>,
+    state: Vec<u64>,
+}
+
+#[derive(PartialEq, Hash)]
+struct ComputePipelineKey {
+    layout: wgn::PipelineLayoutId,
+    compute_stage: StageKey,
+}
+
+/// Per-device state of the `create_*_cached` methods, once enabled.
+///
+/// Samplers and bind group layouts are keyed by their fields flattened into words.
+#[derive(Default)]
+pub(crate) struct ObjectCache {
+    stats: ObjectCacheStats,
+    samplers: Objects<Vec<u64>, Sampler>,
+    bind_group_layouts: Objects<Vec<u64>, BindGroupLayout>,
+    bind_groups: Objects<BindGroupKey, BindGroup>,
+    pipeline_layouts: Objects<Vec<wgn::BindGroupLayoutId>, PipelineLayout>,
+    render_pipelines: Objects<RenderPipelineKey, RenderPipeline>,
+    compute_pipelines: Objects<ComputePipelineKey, ComputePipeline>,
+}
+
+fn push_blend(words: &mut Vec<u64>, blend: &BlendDescriptor) {
+    words.extend_from_slice(&[
+        blend.src_factor as u64,
+        blend.dst_factor as u64,
+        blend.operation as u64,
+    ]);
+}
+
+fn push_stencil_face(words: &mut Vec<u64>, face: &StencilStateFaceDescriptor) {
+    words.extend_from_slice(&[
+        face.compare as u64,
+        face.fail_op as u64,
+        face.depth_fail_op as u64,
+        face.pass_op as u64,
+    ]);
+}
+
+impl Device {
+    /// Deduplicate the objects created through the `create_*_cached` methods.
+    ///
+    /// Objects are keyed by a copy of their descriptor, including the ids of the
+    /// objects it refers to but not its label, and shared between all equal
+    /// descriptors for as long as a handle to them is alive. Without the cache,
+    /// the `create_*_cached` methods create a new object every time.
+    pub fn enable_object_cache(&mut self) {
+        if self.objects.is_none() {
+            self.objects = Some(Mutex::new(ObjectCache::default()));
+        }
+    }
+
+    /// Hits and misses of the object cache, if it is enabled.
+    pub fn object_cache_stats(&self) -> Option<ObjectCacheStats> {
+        self.objects
+            .as_ref()
+            .map(|objects| objects.lock().unwrap().stats)
+    }
+
+    fn get_or_create<K: Hash + PartialEq, T>(
+        &self,
+        objects: fn(&mut ObjectCache) -> &mut Objects<K, T>,
+        key: K,
+        create: impl FnOnce() -> T,
+    ) -> Arc<T> {
+        let cache = match self.objects {
+            Some(ref cache) => cache,
+            None => return Arc::new(create()),
+        };
+        {
+            let mut cache = cache.lock().unwrap();
+            if let Some(object) = objects(&mut cache).get(&key) {
+                cache.stats.hits += 1;
+                return object;
+            }
+        }
+        // The lock is not held during creation, which panics on invalid descriptors.
+        let object = Arc::new(create());
+        let mut cache = cache.lock().unwrap();
+        cache.stats.misses += 1;
+        objects(&mut cache).insert(key, &object);
+        object
+    }
+
+    pub fn create_sampler_cached(&self, desc: &SamplerDescriptor) -> Arc<Sampler> {
+        let key = vec![
+            desc.r_address_mode as u64,
+            desc.s_address_mode as u64,
+            desc.t_address_mode as u64,
+            desc.mag_filter as u64,
+            desc.min_filter as u64,
+            desc.mipmap_filter as u64,
+            u64::from(desc.lod_min_clamp.to_bits()),
+            u64::from(desc.lod_max_clamp.to_bits()),
+            u64::from(desc.max_anisotropy),
+            desc.compare_function as u64,
+            desc.border_color as u64,
+        ];
+        self.get_or_create(
+            |cache| &mut cache.samplers,
+            key,
+            || self.create_sampler(desc),
+        )
+    }
+
+    pub fn create_bind_group_layout_cached(
+        &self,
+        desc: &BindGroupLayoutDescriptor,
+    ) -> Arc<BindGroupLayout> {
+        let mut key = Vec::with_capacity(3 * desc.bindings.len());
+        for binding in desc.bindings {
+            key.extend_from_slice(&[
+                u64::from(binding.binding),
+                u64::from(binding.visibility.bits()),
+                binding.ty as u64,
+            ]);
+        }
+        self.get_or_create(
+            |cache| &mut cache.bind_group_layouts,
+            key,
+            || self.create_bind_group_layout(desc),
+        )
+    }
+
+    pub fn create_bind_group_cached(&self, desc: &BindGroupDescriptor) -> Arc<BindGroup> {
+        let key = BindGroupKey {
+            layout: desc.layout.id,
+            bindings: desc
+                .bindings
+                .iter()
+                .map(|binding| {
+                    let resource = match binding.resource {
+                        BindingResource::Buffer { buffer, ref range } => {
+                            BindingKey::Buffer(buffer.id, range.clone())
+                        }
+                        BindingResource::Sampler(sampler) => BindingKey::Sampler(sampler.id),
+                        BindingResource::TextureView(texture_view) => {
+                            BindingKey::TextureView(texture_view.id)
+                        }
+                    };
+                    (binding.binding, resource)
+                })
+                .collect(),
+        };
+        self.get_or_create(
+            |cache| &mut cache.bind_groups,
+            key,
+            || self.create_bind_group(desc),
+        )
+    }
+
+    pub fn create_pipeline_layout_cached(
+        &self,
+        desc: &PipelineLayoutDescriptor,
+    ) -> Arc<PipelineLayout> {
+        let key = desc
+            .bind_group_layouts
+            .iter()
+            .map(|bind_group_layout| bind_group_layout.id)
+            .collect();
+        self.get_or_create(
+            |cache| &mut cache.pipeline_layouts,
+            key,
+            || self.create_pipeline_layout(desc),
+        )
+    }
+
+    pub fn create_render_pipeline_cached(
+        &self,
+        desc: &RenderPipelineDescriptor,
+    ) -> Arc<RenderPipeline> {
+        let rasterization = &desc.rasterization_state;
+        let mut state = vec![
+            rasterization.front_face as u64,
+            rasterization.cull_mode as u64,
+            rasterization.depth_bias as u64,
+            u64::from(rasterization.depth_bias_slope_scale.to_bits()),
+            u64::from(rasterization.depth_bias_clamp.to_bits()),
+            desc.primitive_topology as u64,
+            desc.color_states.len() as u64,
+        ];
+        for color_state in desc.color_states {
+            state.push(color_state.format as u64);
+            push_blend(&mut state, &color_state.color);
+            push_blend(&mut state, &color_state.alpha);
+            state.push(u64::from(color_state.write_mask.bits()));
+        }
+        match desc.depth_stencil_state {
+            Some(ref depth_stencil) => {
+                state.extend_from_slice(&[
+                    1,
+                    depth_stencil.format as u64,
+                    depth_stencil.depth_write_enabled as u64,
+                    depth_stencil.depth_compare as u64,
+                ]);
+                push_stencil_face(&mut state, &depth_stencil.stencil_front);
+                push_stencil_face(&mut state, &depth_stencil.stencil_back);
+                state.push(u64::from(depth_stencil.stencil_read_mask));
+                state.push(u64::from(depth_stencil.stencil_write_mask));
+            }
+            None => state.push(0),
+        }
+        state.push(desc.index_format as u64);
+        state.push(desc.vertex_buffers.len() as u64);
+        for vertex_buffer in desc.vertex_buffers {
+            state.extend_from_slice(&[
+                u64::from(vertex_buffer.stride),
+                vertex_buffer.step_mode as u64,
+                vertex_buffer.attributes.len() as u64,
+            ]);
+            for attribute in vertex_buffer.attributes {
+                state.extend_from_slice(&[
+                    u64::from(attribute.offset),
+                    attribute.format as u64,
+                    u64::from(attribute.attribute_index),
+                ]);
+            }
+        }
+        state.push(u64::from(desc.sample_count));
+
+        let key = RenderPipelineKey {
+            layout: desc.layout.id,
+            vertex_stage: StageKey::new(&desc.vertex_stage),
+            fragment_stage: desc.fragment_stage.as_ref().map(StageKey::new),
+            state,
+        };
+        self.get_or_create(
+            |cache| &mut cache.render_pipelines,
+            key,
+            || self.create_render_pipeline(desc),
+        )
+    }
+
+    pub fn create_compute_pipeline_cached(
+        &self,
+        desc: &ComputePipelineDescriptor,
+    ) -> Arc<ComputePipeline> {
+        let key = ComputePipelineKey {
+            layout: desc.layout.id,
+            compute_stage: StageKey::new(&desc.compute_stage),
+        };
+        self.get_or_create(
+            |cache| &mut cache.compute_pipelines,
+            key,
+            || self.create_compute_pipeline(desc),
+        )
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    BlendDescriptor,
+    ColorStateDescriptor,
+    ColorWriteFlags,
+    CompareFunction,
+    CullMode,
+    DepthStencilStateDescriptor,
+    Device,
+    FrontFace,
+    IndexFormat,
+    PipelineLayout,
+    PipelineStageDescriptor,
+    PrimitiveTopology,
+    RasterizationStateDescriptor,
+    RenderPipeline,
+    RenderPipelineDescriptor,
+    StencilStateFaceDescriptor,
+    TextureFormat,
+    VertexBufferDescriptor,
+};
+
+/// Counter-clockwise front faces, without culling or depth bias.
+///
+/// The state descriptors are re-exported from wgpu-native, so they cannot
+/// implement `Default` here.
+pub fn default_rasterization_state() -> RasterizationStateDescriptor {
+    RasterizationStateDescriptor {
+        front_face: FrontFace::Ccw,
+        cull_mode: CullMode::None,
+        depth_bias: 0,
+        depth_bias_slope_scale: 0.0,
+        depth_bias_clamp: 0.0,
+    }
+}
+
+/// A color target of the given format, written without blending.
+pub fn default_color_state(format: TextureFormat) -> ColorStateDescriptor {
+    ColorStateDescriptor {
+        format,
+        color: BlendDescriptor::REPLACE,
+        alpha: BlendDescriptor::REPLACE,
+        write_mask: ColorWriteFlags::ALL,
+    }
+}
+
+/// A depth target of the given format, tested with `Less` and written, with
+/// stencil left untouched.
+pub fn default_depth_stencil_state(format: TextureFormat) -> DepthStencilStateDescriptor {
+    DepthStencilStateDescriptor {
+        format,
+        depth_write_enabled: true,
+        depth_compare: CompareFunction::Less,
+        stencil_front: StencilStateFaceDescriptor::IGNORE,
+        stencil_back: StencilStateFaceDescriptor::IGNORE,
+        stencil_read_mask: 0,
+        stencil_write_mask: 0,
+    }
+}
+
+/// Builder for a `RenderPipelineDescriptor`, starting from common defaults.
+///
+/// By default the pipeline draws a triangle list with 16-bit indices and no culling,
+/// without a fragment stage, color targets, depth testing or multisampling.
+pub struct RenderPipelineBuilder<'a> {
+    label: Option<&'a str>,
+    layout: &'a PipelineLayout,
+    vertex_stage: PipelineStageDescriptor<'a>,
+    fragment_stage: Option<PipelineStageDescriptor<'a>>,
+    rasterization_state: RasterizationStateDescriptor,
+    primitive_topology: PrimitiveTopology,
+    color_states: Vec<ColorStateDescriptor>,
+    depth_stencil_state: Option<DepthStencilStateDescriptor>,
+    index_format: IndexFormat,
+    vertex_buffers: Vec<VertexBufferDescriptor<'a>>,
+    sample_count: u32,
+}
+
+impl<'a> RenderPipelineBuilder<'a> {
+    pub fn new(layout: &'a PipelineLayout, vertex_stage: PipelineStageDescriptor<'a>) -> Self {
+        RenderPipelineBuilder {
+            label: None,
+            layout,
+            vertex_stage,
+            fragment_stage: None,
+            rasterization_state: default_rasterization_state(),
+            primitive_topology: PrimitiveTopology::TriangleList,
+            color_states: Vec::new(),
+            depth_stencil_state: None,
+            index_format: IndexFormat::Uint16,
+            vertex_buffers: Vec::new(),
+            sample_count: 1,
+        }
+    }
+
+    pub fn label(mut self, label: &'a str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Set the fragment stage, which pipelines with color targets need.
+    pub fn fragment_stage(mut self, stage: PipelineStageDescriptor<'a>) -> Self {
+        self.fragment_stage = Some(stage);
+        self
+    }
+
+    pub fn rasterization_state(mut self, state: RasterizationStateDescriptor) -> Self {
+        self.rasterization_state = state;
+        self
+    }
+
+    pub fn front_face(mut self, front_face: FrontFace) -> Self {
+        self.rasterization_state.front_face = front_face;
+        self
+    }
+
+    pub fn cull_mode(mut self, cull_mode: CullMode) -> Self {
+        self.rasterization_state.cull_mode = cull_mode;
+        self
+    }
+
+    pub fn primitive_topology(mut self, topology: PrimitiveTopology) -> Self {
+        self.primitive_topology = topology;
+        self
+    }
+
+    /// Add a color target with the given state.
+    pub fn color_state(mut self, state: ColorStateDescriptor) -> Self {
+        self.color_states.push(state);
+        self
+    }
+
+    /// Add a color target that is written without blending.
+    pub fn color_target(self, format: TextureFormat) -> Self {
+        self.color_state(default_color_state(format))
+    }
+
+    pub fn depth_stencil_state(mut self, state: DepthStencilStateDescriptor) -> Self {
+        self.depth_stencil_state = Some(state);
+        self
+    }
+
+    /// Test against and write to a depth target, leaving stencil untouched.
+    pub fn depth_test(self, format: TextureFormat, compare: CompareFunction) -> Self {
+        self.depth_stencil_state(DepthStencilStateDescriptor {
+            depth_compare: compare,
+            ..default_depth_stencil_state(format)
+        })
+    }
+
+    pub fn index_format(mut self, format: IndexFormat) -> Self {
+        self.index_format = format;
+        self
+    }
+
+    /// Add a vertex buffer, bound at the next slot.
+    pub fn vertex_buffer(mut self, buffer: VertexBufferDescriptor<'a>) -> Self {
+        self.vertex_buffers.push(buffer);
+        self
+    }
+
+    pub fn sample_count(mut self, sample_count: u32) -> Self {
+        self.sample_count = sample_count;
+        self
+    }
+
+    /// The descriptor that `build` creates the pipeline from.
+    pub fn descriptor(&self) -> RenderPipelineDescriptor<'_> {
+        RenderPipelineDescriptor {
+            label: self.label,
+            layout: self.layout,
+            vertex_stage: self.vertex_stage.clone(),
+            fragment_stage: self.fragment_stage.clone(),
+            rasterization_state: self.rasterization_state.clone(),
+            primitive_topology: self.primitive_topology,
+            color_states: &self.color_states,
+            depth_stencil_state: self.depth_stencil_state.clone(),
+            index_format: self.index_format,
+            vertex_buffers: &self.vertex_buffers,
+            sample_count: self.sample_count,
+        }
+    }
+
+    pub fn build(&self, device: &Device) -> RenderPipeline {
+        device.create_render_pipeline(&self.descriptor())
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{
+    BufferCopyView,
+    BufferDescriptor,
+    BufferMapAsyncResult,
+    BufferUsageFlags,
+    CommandEncoderDescriptor,
+    Device,
+    Origin3d,
+    Texture,
+    TextureCopyView,
+    TextureFormat,
+    TextureFormatExt,
+};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Alignment required for the `row_pitch` of copies between buffers and textures.
+pub const COPY_ROW_PITCH_ALIGNMENT: u32 = 256;
+
+pub(crate) fn align_row_pitch(row_size: u32) -> u32 {
+    (row_size + COPY_ROW_PITCH_ALIGNMENT - 1) & !(COPY_ROW_PITCH_ALIGNMENT - 1)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextureReadError {
+    /// The format has no fixed texel size that can be read back.
+    UnsupportedFormat(TextureFormat),
+    /// The mip level is not present in the texture.
+    InvalidLevel(u32),
+    /// The array slice is not present in the texture.
+    InvalidSlice(u32),
+    /// The staging buffer could not be mapped for reading.
+    MapFailed,
+}
+
+impl Texture {
+    /// Copy a single mip level and array slice into a staging buffer, calling `callback`
+    /// with tightly packed texel rows once the copy has completed.
+    ///
+    /// The callback fires from `Device::poll`, like any other buffer mapping. The device is
+    /// borrowed mutably because the copy is submitted through `Device::get_queue`.
+    pub fn read_to_vec_async<F>(&self, device: &mut Device, level: u32, slice: u32, callback: F)
+    where
+        F: FnOnce(Result<Vec<u8>, TextureReadError>) + 'static,
+    {
+        let info = self.format.info();
+        if !info.is_color() || info.block_dimensions != (1, 1) {
+            return callback(Err(TextureReadError::UnsupportedFormat(self.format)));
+        }
+        if level >= self.mip_level_count {
+            return callback(Err(TextureReadError::InvalidLevel(level)));
+        }
+        if slice >= self.array_size {
+            return callback(Err(TextureReadError::InvalidSlice(slice)));
+        }
+
+        let extent = self.level_size(level);
+        let row_size = info.block_size * extent.width;
+        let row_pitch = align_row_pitch(row_size);
+        let rows = extent.height * extent.depth;
+
+        let staging_buffer = Rc::new(device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: row_pitch * rows,
+            usage: BufferUsageFlags::MAP_READ | BufferUsageFlags::TRANSFER_DST,
+        }));
+
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor { label: None });
+        encoder.copy_texture_to_buffer(
+            TextureCopyView {
+                texture: self,
+                level,
+                slice,
+                origin: Origin3d {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+            },
+            BufferCopyView {
+                buffer: &staging_buffer,
+                offset: 0,
+                row_pitch,
+                image_height: extent.height,
+            },
+            extent,
+        );
+        device.get_queue().submit(&[encoder.finish()]);
+
+        // The staging buffer has to outlive the mapping, so the callback keeps it alive.
+        let keep_alive = Rc::clone(&staging_buffer);
+        staging_buffer.map_read_async(
+            0,
+            row_pitch * rows,
+            move |result: BufferMapAsyncResult<&[u8]>| {
+                let texels = match result {
+                    Ok(mapping) => {
+                        let mut texels = Vec::with_capacity((row_size * rows) as usize);
+                        for row in mapping.data.chunks(row_pitch as usize) {
+                            texels.extend_from_slice(&row[.. row_size as usize]);
+                        }
+                        Ok(texels)
+                    }
+                    Err(()) => Err(TextureReadError::MapFailed),
+                };
+                drop(keep_alive);
+                callback(texels);
+            },
+        );
+    }
+
+    /// Read a single mip level and array slice back to the CPU, blocking until the GPU
+    /// has finished all submitted work.
+    ///
+    /// Rows are returned tightly packed, without the padding required by the copy.
+    pub fn read_to_vec(
+        &self,
+        device: &mut Device,
+        level: u32,
+        slice: u32,
+    ) -> Result<Vec<u8>, TextureReadError> {
+        let result = Rc::new(RefCell::new(None));
+        let result_ref = Rc::clone(&result);
+        self.read_to_vec_async(device, level, slice, move |texels| {
+            *result_ref.borrow_mut() = Some(texels);
+        });
+        device.poll(true);
+        let texels = result.borrow_mut().take();
+        texels.unwrap_or(Err(TextureReadError::MapFailed))
+    }
+}
@@ -0,0 +1,452 @@
+//! Reflection of descriptor bindings and vertex inputs from SPIR-V modules.
+
+use crate::{
+    BindGroupLayout,
+    BindGroupLayoutBinding,
+    BindGroupLayoutDescriptor,
+    BindingType,
+    Device,
+    InputStepMode,
+    PipelineLayout,
+    PipelineLayoutDescriptor,
+    ShaderModule,
+    ShaderStage,
+    ShaderStageFlags,
+    VertexAttributeDescriptor,
+    VertexBufferDescriptor,
+    VertexFormat,
+    VertexFormatExt,
+};
+
+use wgpu_shader::{HEADER_WORDS, SPIRV_MAGIC};
+
+use std::collections::HashMap;
+
+const OP_ENTRY_POINT: u32 = 15;
+const OP_EXECUTION_MODE: u32 = 16;
+const OP_TYPE_INT: u32 = 21;
+const OP_TYPE_FLOAT: u32 = 22;
+const OP_TYPE_VECTOR: u32 = 23;
+const OP_TYPE_IMAGE: u32 = 25;
+const OP_TYPE_SAMPLER: u32 = 26;
+const OP_TYPE_STRUCT: u32 = 30;
+const OP_TYPE_POINTER: u32 = 32;
+const OP_VARIABLE: u32 = 59;
+const OP_DECORATE: u32 = 71;
+
+const EXECUTION_MODEL_VERTEX: u32 = 0;
+const EXECUTION_MODEL_FRAGMENT: u32 = 4;
+const EXECUTION_MODEL_GL_COMPUTE: u32 = 5;
+const EXECUTION_MODE_LOCAL_SIZE: u32 = 17;
+
+const DECORATION_BLOCK: u32 = 2;
+const DECORATION_BUFFER_BLOCK: u32 = 3;
+const DECORATION_BUILT_IN: u32 = 11;
+const DECORATION_LOCATION: u32 = 30;
+const DECORATION_BINDING: u32 = 33;
+const DECORATION_DESCRIPTOR_SET: u32 = 34;
+
+const STORAGE_CLASS_UNIFORM_CONSTANT: u32 = 0;
+const STORAGE_CLASS_INPUT: u32 = 1;
+const STORAGE_CLASS_UNIFORM: u32 = 2;
+const STORAGE_CLASS_STORAGE_BUFFER: u32 = 12;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReflectError {
+    /// The data does not start with the SPIR-V magic number.
+    InvalidMagic,
+    /// An instruction overruns the end of the module.
+    Truncated,
+    /// The module declares no entry point.
+    NoEntryPoint,
+    /// The entry point uses an execution model other than vertex, fragment or compute.
+    UnsupportedStage(u32),
+    /// The resource at this set and binding has a type with no `BindingType`.
+    UnsupportedBinding { set: u32, binding: u32 },
+    /// The vertex input at this location has a type with no `VertexFormat`.
+    UnsupportedInput { location: u32 },
+    /// Two shaders declare different types for the same set and binding.
+    ConflictingBinding { set: u32, binding: u32 },
+}
+
+/// A resource binding declared by a shader.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReflectedBinding {
+    pub set: u32,
+    pub binding: u32,
+    pub ty: BindingType,
+}
+
+/// A vertex shader input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VertexInput {
+    pub location: u32,
+    pub format: VertexFormat,
+}
+
+/// Interface of the first entry point of a SPIR-V module.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShaderReflection {
+    pub stage: ShaderStage,
+    pub entry_point: String,
+    /// Resource bindings, ordered by set and binding.
+    pub bindings: Vec<ReflectedBinding>,
+    /// Vertex inputs ordered by location. Empty for other stages.
+    pub vertex_inputs: Vec<VertexInput>,
+    /// Workgroup size of compute shaders.
+    pub local_size: Option<[u32; 3]>,
+}
+
+/// Vertex buffer layout with all inputs of a vertex shader packed in location order.
+#[derive(Clone, Debug)]
+pub struct ReflectedVertexLayout {
+    pub stride: u32,
+    pub attributes: Vec<VertexAttributeDescriptor>,
+}
+
+impl ReflectedVertexLayout {
+    pub fn descriptor(&self, step_mode: InputStepMode) -> VertexBufferDescriptor<'_> {
+        VertexBufferDescriptor {
+            stride: self.stride,
+            step_mode,
+            attributes: &self.attributes,
+        }
+    }
+}
+
+impl ShaderReflection {
+    /// Interleaved vertex layout for a single vertex buffer feeding every input.
+    pub fn vertex_layout(&self) -> ReflectedVertexLayout {
+        let mut stride = 0;
+        let attributes = self
+            .vertex_inputs
+            .iter()
+            .map(|input| {
+                let attribute = VertexAttributeDescriptor {
+                    offset: stride,
+                    format: input.format,
+                    attribute_index: input.location,
+                };
+                stride += input.format.size();
+                attribute
+            })
+            .collect();
+        ReflectedVertexLayout { stride, attributes }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Type {
+    Scalar {
+        float: bool,
+        signed: bool,
+        width: u32,
+    },
+    Vector {
+        component: u32,
+        count: u32,
+    },
+    Image {
+        sampled: u32,
+    },
+    Sampler,
+    Struct,
+    Pointer {
+        pointee: u32,
+    },
+}
+
+#[derive(Default)]
+struct Decorations {
+    set: Option<u32>,
+    binding: Option<u32>,
+    location: Option<u32>,
+    built_in: bool,
+    block: bool,
+    buffer_block: bool,
+}
+
+/// Decode a nul terminated literal string.
+fn read_string(words: &[u32]) -> String {
+    let bytes = words
+        .iter()
+        .flat_map(|word| (0 .. 4).map(move |i| (word >> (i * 8)) as u8))
+        .take_while(|&byte| byte != 0)
+        .collect::<Vec<_>>();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn vertex_format(types: &HashMap<u32, Type>, id: u32) -> Option<VertexFormat> {
+    let (component, count) = match *types.get(&id)? {
+        Type::Vector { component, count } => (component, count),
+        Type::Scalar { .. } => (id, 1),
+        _ => return None,
+    };
+    let (float, signed) = match *types.get(&component)? {
+        Type::Scalar {
+            float,
+            signed,
+            width: 32,
+        } => (float, signed),
+        _ => return None,
+    };
+    Some(match (float, signed, count) {
+        (true, _, 1) => VertexFormat::Float,
+        (true, _, 2) => VertexFormat::Float2,
+        (true, _, 3) => VertexFormat::Float3,
+        (true, _, 4) => VertexFormat::Float4,
+        (false, true, 1) => VertexFormat::Int,
+        (false, true, 2) => VertexFormat::Int2,
+        (false, true, 3) => VertexFormat::Int3,
+        (false, true, 4) => VertexFormat::Int4,
+        (false, false, 1) => VertexFormat::Uint,
+        (false, false, 2) => VertexFormat::Uint2,
+        (false, false, 3) => VertexFormat::Uint3,
+        (false, false, 4) => VertexFormat::Uint4,
+        _ => return None,
+    })
+}
+
+/// Reflect the interface of the first entry point of a SPIR-V module.
+///
+/// Bindings of all resources declared in the module are reported, whether or not the
+/// entry point uses them.
+pub fn reflect_spirv(words: &[u32]) -> Result<ShaderReflection, ReflectError> {
+    if words.first() != Some(&SPIRV_MAGIC) {
+        return Err(ReflectError::InvalidMagic);
+    }
+    if words.len() < HEADER_WORDS {
+        return Err(ReflectError::Truncated);
+    }
+
+    let mut entry_point = None;
+    let mut local_sizes = HashMap::new();
+    let mut types = HashMap::new();
+    let mut decorations = HashMap::<u32, Decorations>::new();
+    let mut variables = Vec::new();
+
+    let mut offset = HEADER_WORDS;
+    while offset < words.len() {
+        let word_count = (words[offset] >> 16) as usize;
+        let opcode = words[offset] & 0xFFFF;
+        if word_count == 0 || offset + word_count > words.len() {
+            return Err(ReflectError::Truncated);
+        }
+        let operands = &words[offset + 1 .. offset + word_count];
+        offset += word_count;
+
+        let operand = |i: usize| operands.get(i).cloned().ok_or(ReflectError::Truncated);
+        match opcode {
+            OP_ENTRY_POINT if entry_point.is_none() => {
+                let name = read_string(operands.get(2 ..).unwrap_or(&[]));
+                entry_point = Some((operand(0)?, operand(1)?, name));
+            }
+            OP_EXECUTION_MODE if operand(1)? == EXECUTION_MODE_LOCAL_SIZE => {
+                local_sizes.insert(operand(0)?, [operand(2)?, operand(3)?, operand(4)?]);
+            }
+            OP_TYPE_INT => {
+                let ty = Type::Scalar {
+                    float: false,
+                    signed: operand(2)? != 0,
+                    width: operand(1)?,
+                };
+                types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_FLOAT => {
+                let ty = Type::Scalar {
+                    float: true,
+                    signed: true,
+                    width: operand(1)?,
+                };
+                types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_VECTOR => {
+                let ty = Type::Vector {
+                    component: operand(1)?,
+                    count: operand(2)?,
+                };
+                types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_IMAGE => {
+                let ty = Type::Image {
+                    sampled: operand(6)?,
+                };
+                types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_SAMPLER => {
+                types.insert(operand(0)?, Type::Sampler);
+            }
+            OP_TYPE_STRUCT => {
+                types.insert(operand(0)?, Type::Struct);
+            }
+            OP_TYPE_POINTER => {
+                types.insert(
+                    operand(0)?,
+                    Type::Pointer {
+                        pointee: operand(2)?,
+                    },
+                );
+            }
+            OP_VARIABLE => {
+                variables.push((operand(0)?, operand(1)?, operand(2)?));
+            }
+            OP_DECORATE => {
+                let target = decorations.entry(operand(0)?).or_default();
+                match operand(1)? {
+                    DECORATION_BLOCK => target.block = true,
+                    DECORATION_BUFFER_BLOCK => target.buffer_block = true,
+                    DECORATION_BUILT_IN => target.built_in = true,
+                    DECORATION_LOCATION => target.location = Some(operand(2)?),
+                    DECORATION_BINDING => target.binding = Some(operand(2)?),
+                    DECORATION_DESCRIPTOR_SET => target.set = Some(operand(2)?),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let (execution_model, entry_id, entry_name) = entry_point.ok_or(ReflectError::NoEntryPoint)?;
+    let stage = match execution_model {
+        EXECUTION_MODEL_VERTEX => ShaderStage::Vertex,
+        EXECUTION_MODEL_FRAGMENT => ShaderStage::Fragment,
+        EXECUTION_MODEL_GL_COMPUTE => ShaderStage::Compute,
+        other => return Err(ReflectError::UnsupportedStage(other)),
+    };
+
+    let no_decorations = Decorations::default();
+    let mut bindings = Vec::new();
+    let mut vertex_inputs = Vec::new();
+    for &(pointer_type, id, storage_class) in &variables {
+        let decoration = decorations.get(&id).unwrap_or(&no_decorations);
+        let pointee = match types.get(&pointer_type) {
+            Some(&Type::Pointer { pointee }) => pointee,
+            _ => continue,
+        };
+        let pointee_decoration = decorations.get(&pointee).unwrap_or(&no_decorations);
+
+        if let (Some(set), Some(binding)) = (decoration.set, decoration.binding) {
+            let unsupported = ReflectError::UnsupportedBinding { set, binding };
+            let ty = match (storage_class, types.get(&pointee)) {
+                (STORAGE_CLASS_UNIFORM, Some(&Type::Struct)) if pointee_decoration.block => {
+                    BindingType::UniformBuffer
+                }
+                (STORAGE_CLASS_UNIFORM, Some(&Type::Struct)) if pointee_decoration.buffer_block => {
+                    BindingType::StorageBuffer
+                }
+                (STORAGE_CLASS_STORAGE_BUFFER, Some(&Type::Struct)) => BindingType::StorageBuffer,
+                (STORAGE_CLASS_UNIFORM_CONSTANT, Some(&Type::Sampler)) => BindingType::Sampler,
+                (STORAGE_CLASS_UNIFORM_CONSTANT, Some(&Type::Image { sampled: 1 })) => {
+                    BindingType::SampledTexture
+                }
+                _ => return Err(unsupported),
+            };
+            bindings.push(ReflectedBinding { set, binding, ty });
+        } else if storage_class == STORAGE_CLASS_INPUT
+            && stage == ShaderStage::Vertex
+            && !decoration.built_in
+        {
+            if let Some(location) = decoration.location {
+                let format = vertex_format(&types, pointee)
+                    .ok_or(ReflectError::UnsupportedInput { location })?;
+                vertex_inputs.push(VertexInput { location, format });
+            }
+        }
+    }
+    bindings.sort_by_key(|b| (b.set, b.binding));
+    vertex_inputs.sort_by_key(|input| input.location);
+
+    Ok(ShaderReflection {
+        stage,
+        entry_point: entry_name,
+        bindings,
+        vertex_inputs,
+        local_size: local_sizes.get(&entry_id).cloned(),
+    })
+}
+
+/// Layouts created from the bindings of a set of shaders.
+pub struct ReflectedPipelineLayout {
+    /// One layout per descriptor set, including empty layouts for unused sets.
+    pub bind_group_layouts: Vec<BindGroupLayout>,
+    pub pipeline_layout: PipelineLayout,
+}
+
+/// Merge the bindings of several shaders into layout bindings, grouped by set.
+///
+/// Bindings shared between shaders have to agree on their type, and become visible
+/// to all of their stages.
+pub fn merge_bindings(
+    reflections: &[ShaderReflection],
+) -> Result<Vec<Vec<BindGroupLayoutBinding>>, ReflectError> {
+    let mut sets = Vec::<Vec<BindGroupLayoutBinding>>::new();
+    for reflection in reflections {
+        let visibility = match reflection.stage {
+            ShaderStage::Vertex => ShaderStageFlags::VERTEX,
+            ShaderStage::Fragment => ShaderStageFlags::FRAGMENT,
+            ShaderStage::Compute => ShaderStageFlags::COMPUTE,
+        };
+        for b in &reflection.bindings {
+            while sets.len() <= b.set as usize {
+                sets.push(Vec::new());
+            }
+            let set = &mut sets[b.set as usize];
+            match set.iter_mut().find(|lb| lb.binding == b.binding) {
+                Some(ref lb) if lb.ty != b.ty => {
+                    return Err(ReflectError::ConflictingBinding {
+                        set: b.set,
+                        binding: b.binding,
+                    });
+                }
+                Some(lb) => lb.visibility |= visibility,
+                None => set.push(BindGroupLayoutBinding {
+                    binding: b.binding,
+                    visibility,
+                    ty: b.ty,
+                }),
+            }
+        }
+    }
+    for set in &mut sets {
+        set.sort_by_key(|lb| lb.binding);
+    }
+    Ok(sets)
+}
+
+impl ShaderModule {
+    /// Reflect the interface of the first entry point of this module.
+    pub fn reflect(&self) -> Result<ShaderReflection, ReflectError> {
+        reflect_spirv(&self.spv)
+    }
+}
+
+impl Device {
+    /// Create bind group layouts and a pipeline layout matching the bindings of
+    /// `modules`, which are the stages of a single pipeline.
+    pub fn create_pipeline_layout_from_shaders(
+        &self,
+        modules: &[&ShaderModule],
+    ) -> Result<ReflectedPipelineLayout, ReflectError> {
+        let reflections = modules
+            .iter()
+            .map(|module| module.reflect())
+            .collect::<Result<Vec<_>, _>>()?;
+        let bind_group_layouts = merge_bindings(&reflections)?
+            .iter()
+            .map(|bindings| {
+                self.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: None,
+                    bindings,
+                })
+            })
+            .collect::<Vec<_>>();
+        let pipeline_layout = self.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &bind_group_layouts.iter().collect::<Vec<_>>(),
+        });
+        Ok(ReflectedPipelineLayout {
+            bind_group_layouts,
+            pipeline_layout,
+        })
+    }
+}
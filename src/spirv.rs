@@ -0,0 +1,147 @@
+use crate::{SpecializationConstant, SpecializationValue};
+
+use wgpu_shader::HEADER_WORDS;
+
+use std::collections::HashMap;
+
+const OP_TYPE_INT: u32 = 21;
+const OP_TYPE_FLOAT: u32 = 22;
+const OP_SPEC_CONSTANT_TRUE: u32 = 48;
+const OP_SPEC_CONSTANT_FALSE: u32 = 49;
+const OP_SPEC_CONSTANT: u32 = 50;
+const OP_DECORATE: u32 = 71;
+const DECORATION_SPEC_ID: u32 = 1;
+
+pub(crate) const EMPTY_FRAGMENT_ENTRY_POINT: &str = "main";
+
+/// A fragment shader whose `main` entry point does nothing.
+pub(crate) const EMPTY_FRAGMENT_SHADER: [u32; 32] = [
+    // Magic, version 1.0, generator, id bound and schema.
+    0x0723_0203,
+    0x0001_0000,
+    0,
+    5,
+    0,
+    // OpCapability Shader
+    0x0002_0011,
+    1,
+    // OpMemoryModel Logical GLSL450
+    0x0003_000E,
+    0,
+    1,
+    // OpEntryPoint Fragment %1 "main"
+    0x0005_000F,
+    4,
+    1,
+    0x6E69_616D,
+    0,
+    // OpExecutionMode %1 OriginUpperLeft
+    0x0003_0010,
+    1,
+    7,
+    // %2 = OpTypeVoid
+    0x0002_0013,
+    2,
+    // %3 = OpTypeFunction %2
+    0x0003_0021,
+    3,
+    2,
+    // %1 = OpFunction %2 None %3
+    0x0005_0036,
+    2,
+    1,
+    0,
+    3,
+    // %4 = OpLabel
+    0x0002_00F8,
+    4,
+    // OpReturn
+    0x0001_00FD,
+    // OpFunctionEnd
+    0x0001_0038,
+];
+
+/// Return a copy of a SPIR-V module with the defaults of its specialization constants
+/// replaced by `constants`.
+///
+/// wgpu-native takes no specialization info, so pipeline stages are specialized by
+/// patching their module instead. Constants the module does not declare are ignored.
+///
+/// # Panics
+///
+/// Panics if a value does not match the scalar type declared for its constant.
+pub fn specialize_spirv(words: &[u32], constants: &[SpecializationConstant]) -> Vec<u32> {
+    let mut words = words.to_vec();
+    let mut spec_ids = HashMap::new();
+    // Signedness of integer types, and `None` for float types.
+    let mut scalar_types = HashMap::new();
+
+    let mut offset = HEADER_WORDS.min(words.len());
+    while offset < words.len() {
+        let opcode = words[offset] & 0xFFFF;
+        let word_count = (words[offset] >> 16) as usize;
+        if word_count == 0 || offset + word_count > words.len() {
+            break;
+        }
+        let operands = &words[offset + 1 .. offset + word_count];
+        match opcode {
+            OP_DECORATE if operands.len() >= 3 && operands[1] == DECORATION_SPEC_ID => {
+                spec_ids.insert(operands[0], operands[2]);
+            }
+            OP_TYPE_INT if operands.len() >= 3 => {
+                scalar_types.insert(operands[0], Some(operands[2] != 0));
+            }
+            OP_TYPE_FLOAT if !operands.is_empty() => {
+                scalar_types.insert(operands[0], None);
+            }
+            OP_SPEC_CONSTANT_TRUE | OP_SPEC_CONSTANT_FALSE | OP_SPEC_CONSTANT
+                if operands.len() >= 2 =>
+            {
+                let constant = spec_ids
+                    .get(&operands[1])
+                    .and_then(|&id| constants.iter().find(|constant| constant.id == id));
+                if let Some(constant) = constant {
+                    let ty = scalar_types.get(&operands[0]).cloned();
+                    let value = match (opcode, constant.value, ty) {
+                        (OP_SPEC_CONSTANT_TRUE, SpecializationValue::Bool(value), _)
+                        | (OP_SPEC_CONSTANT_FALSE, SpecializationValue::Bool(value), _) => {
+                            let opcode = if value {
+                                OP_SPEC_CONSTANT_TRUE
+                            } else {
+                                OP_SPEC_CONSTANT_FALSE
+                            };
+                            words[offset] = (word_count as u32) << 16 | opcode;
+                            None
+                        }
+                        (OP_SPEC_CONSTANT, SpecializationValue::Int(value), Some(Some(true))) => {
+                            Some(value as u32)
+                        }
+                        (OP_SPEC_CONSTANT, SpecializationValue::Uint(value), Some(Some(false))) => {
+                            Some(value)
+                        }
+                        (OP_SPEC_CONSTANT, SpecializationValue::Float(value), Some(None)) => {
+                            Some(value.to_bits())
+                        }
+                        _ => panic!(
+                            "Specialization constant {} does not match the type declared in the \
+                             shader: {:?}",
+                            constant.id, constant.value
+                        ),
+                    };
+                    if let Some(value) = value {
+                        assert_eq!(
+                            word_count,
+                            4,
+                            "Specialization constant {} is not a 32-bit scalar",
+                            constant.id
+                        );
+                        words[offset + 3] = value;
+                    }
+                }
+            }
+            _ => {}
+        }
+        offset += word_count;
+    }
+    words
+}
@@ -0,0 +1,105 @@
+/// Memory layout rules of GLSL buffer blocks.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UniformLayout {
+    /// Layout of uniform blocks, rounding the alignment of arrays and structs up to 16 bytes.
+    Std140 = 140,
+    /// Layout of storage blocks, aligning arrays and structs like their members.
+    Std430 = 430,
+}
+
+/// A `#[repr(C)]` struct whose memory layout matches a GLSL block.
+///
+/// With the "derive" feature, `#[derive(Uniform)]` checks at compile time that every
+/// field lies at the offset GLSL gives the corresponding block member, and that the
+/// size of the struct matches the size of the block, so that arrays of it match too.
+/// A mismatch fails the build with an error pointing at the field, such as "expected an
+/// array with a size of 16, found one with a size of 12", where the first number is
+/// the offset (or size) expected by GLSL and the second the one of the Rust struct.
+///
+/// Field types map to GLSL types as follows:
+///
+/// - `f32`, `i32` and `u32` are scalars,
+/// - arrays of two to four scalars, such as `[f32; 3]`, are vectors,
+/// - other arrays are GLSL arrays, so `[[f32; 4]; 4]` is a `mat4`,
+/// - any other type has to implement `Uniform` itself, with the same layout.
+///
+/// Fields take options in a `#[uniform(...)]` attribute:
+///
+/// - `glsl = "mat4"` sets the GLSL type of a field, for types such as math library
+///   matrices. Vectors, `matN`, `matCxR` and arrays of them like `vec4[2]` are accepted.
+///   Arrays of two to four scalars are always taken for vectors, so a `[u32; 4]` field
+///   is checked as a `uvec4`. A block member declared as `uint[4]` needs
+///   `glsl = "uint[4]"`, or the check compares against the wrong layout,
+/// - `padding` marks a field only present to move the next fields to their offsets.
+///
+/// A `#[uniform(std430)]` attribute on the struct checks against the std430 layout
+/// instead of std140.
+pub trait Uniform: Copy {
+    const LAYOUT: UniformLayout;
+    /// Alignment of the struct within a block.
+    const ALIGN: usize;
+}
+
+/// Layouts that `#[derive(Uniform)]` rejects at compile time.
+///
+/// A `vec3` is aligned to 16 bytes, so it cannot directly follow a `vec2`:
+///
+/// ```compile_fail,E0308
+/// #[repr(C)]
+/// #[derive(Clone, Copy, wgpu::Uniform)]
+/// struct Vertex {
+///     uv: [f32; 2],
+///     normal: [f32; 3],
+/// }
+/// ```
+///
+/// In std140, the elements of a `vec3[2]` are 16 bytes apart, not 12:
+///
+/// ```compile_fail,E0308
+/// #[repr(C)]
+/// #[derive(Clone, Copy, wgpu::Uniform)]
+/// struct Triangle {
+///     normals: [[f32; 3]; 2],
+/// }
+/// ```
+///
+/// A nested struct has to use the layout of the block containing it:
+///
+/// ```compile_fail,E0308
+/// #[repr(C)]
+/// #[derive(Clone, Copy, wgpu::Uniform)]
+/// #[uniform(std430)]
+/// struct Material {
+///     color: [f32; 4],
+/// }
+///
+/// #[repr(C)]
+/// #[derive(Clone, Copy, wgpu::Uniform)]
+/// struct Globals {
+///     material: Material,
+/// }
+/// ```
+///
+/// The layout of a generic struct cannot be checked:
+///
+/// ```compile_fail
+/// #[repr(C)]
+/// #[derive(Clone, Copy, wgpu::Uniform)]
+/// struct Wrapper<T: Copy> {
+///     value: T,
+/// }
+/// ```
+#[cfg(all(doctest, feature = "derive"))]
+pub struct RejectedLayouts;
+
+/// Round `offset` up to a multiple of `alignment`, which is a power of two.
+#[doc(hidden)]
+pub const fn align_to(offset: usize, alignment: usize) -> usize {
+    (offset + alignment - 1) & !(alignment - 1)
+}
+
+#[doc(hidden)]
+pub const fn max(a: usize, b: usize) -> usize {
+    a * (a >= b) as usize + b * (a < b) as usize
+}
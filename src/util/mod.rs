@@ -0,0 +1,10 @@
+//! Higher level helpers built on top of the core API.
+
+mod watcher;
+
+pub use self::watcher::{
+    ComputePipelineHandle,
+    RenderPipelineHandle,
+    ShaderLoadError,
+    ShaderWatcher,
+};
@@ -0,0 +1,326 @@
+#[cfg(feature = "glsl")]
+use crate::{compile_preprocessed_glsl, preprocess, FileResolver, GlslError, PreprocessError};
+use crate::{read_spirv, ComputePipeline, Device, RenderPipeline, ShaderModule, ShaderStage};
+
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Debug)]
+pub enum ShaderLoadError {
+    /// The shader file could not be read, or is not a valid SPIR-V module.
+    Io(io::Error),
+    /// The file extension names no shader stage. Expected are `.vert`, `.frag` and
+    /// `.comp`, optionally followed by `.spv` for SPIR-V.
+    UnknownStage,
+    /// GLSL sources can only be loaded with the "glsl" feature.
+    GlslNotEnabled,
+    #[cfg(feature = "glsl")]
+    Preprocess(PreprocessError),
+    #[cfg(feature = "glsl")]
+    Glsl(GlslError),
+    /// The closure building the pipeline returned an error.
+    Pipeline(Box<dyn Error>),
+}
+
+/// Handle to a render pipeline owned by a `ShaderWatcher`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RenderPipelineHandle(usize);
+
+/// Handle to a compute pipeline owned by a `ShaderWatcher`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ComputePipelineHandle(usize);
+
+struct WatchedShader {
+    path: PathBuf,
+    /// The shader file and the files it includes, with their modification times.
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+    module: ShaderModule,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Status {
+    Unchanged,
+    Reloaded,
+    Failed,
+}
+
+type BuildPipeline<P> = dyn Fn(&Device, &[&ShaderModule]) -> Result<P, Box<dyn Error>>;
+
+struct WatchedPipeline<P> {
+    shaders: Vec<usize>,
+    build: Box<BuildPipeline<P>>,
+    pipeline: P,
+}
+
+impl<P> WatchedPipeline<P> {
+    fn new<F, E>(
+        device: &Device,
+        shaders: Vec<usize>,
+        modules: &[&ShaderModule],
+        build: F,
+    ) -> Result<Self, ShaderLoadError>
+    where
+        F: Fn(&Device, &[&ShaderModule]) -> Result<P, E> + 'static,
+        E: Into<Box<dyn Error>>,
+    {
+        let build: Box<BuildPipeline<P>> =
+            Box::new(move |device, modules| build(device, modules).map_err(Into::into));
+        let pipeline = build(device, modules).map_err(ShaderLoadError::Pipeline)?;
+        Ok(WatchedPipeline {
+            shaders,
+            build,
+            pipeline,
+        })
+    }
+
+    /// Rebuild the pipeline if any of its shaders changed, unless one failed to load.
+    ///
+    /// A build error keeps the previous pipeline, and is reported against the first of
+    /// its shaders that was reloaded.
+    fn rebuild(
+        &mut self,
+        device: &Device,
+        shaders: &[WatchedShader],
+        status: &[Status],
+    ) -> Option<(PathBuf, ShaderLoadError)> {
+        let statuses = self.shaders.iter().map(|&index| status[index]);
+        if statuses.clone().all(|s| s != Status::Reloaded)
+            || statuses.clone().any(|s| s == Status::Failed)
+        {
+            return None;
+        }
+        let modules = self
+            .shaders
+            .iter()
+            .map(|&index| &shaders[index].module)
+            .collect::<Vec<_>>();
+        match (self.build)(device, &modules) {
+            Ok(pipeline) => {
+                self.pipeline = pipeline;
+                None
+            }
+            Err(err) => {
+                let reloaded = self
+                    .shaders
+                    .iter()
+                    .find(|&&index| status[index] == Status::Reloaded)?;
+                Some((shaders[*reloaded].path.clone(), ShaderLoadError::Pipeline(err)))
+            }
+        }
+    }
+}
+
+fn shader_stage(path: &Path) -> Option<(ShaderStage, bool)> {
+    let mut extension = path.extension()?.to_str()?;
+    let spirv = extension == "spv";
+    if spirv {
+        extension = Path::new(path.file_stem()?).extension()?.to_str()?;
+    }
+    let stage = match extension {
+        "vert" => ShaderStage::Vertex,
+        "frag" => ShaderStage::Fragment,
+        "comp" => ShaderStage::Compute,
+        _ => return None,
+    };
+    Some((stage, spirv))
+}
+
+/// Load a shader module, along with the paths of the files it includes.
+fn load_shader(
+    device: &Device,
+    path: &Path,
+) -> Result<(ShaderModule, Vec<PathBuf>), ShaderLoadError> {
+    let (stage, spirv) = shader_stage(path).ok_or(ShaderLoadError::UnknownStage)?;
+    if spirv {
+        let spv = fs::File::open(path)
+            .and_then(read_spirv)
+            .map_err(ShaderLoadError::Io)?;
+        Ok((device.create_shader_module(&spv), Vec::new()))
+    } else {
+        load_glsl(device, path, stage)
+    }
+}
+
+#[cfg(feature = "glsl")]
+fn load_glsl(
+    device: &Device,
+    path: &Path,
+    stage: ShaderStage,
+) -> Result<(ShaderModule, Vec<PathBuf>), ShaderLoadError> {
+    let source = fs::read_to_string(path).map_err(ShaderLoadError::Io)?;
+    let name = path.to_string_lossy();
+    let preprocessed = preprocess(&name, &source, &[], &mut FileResolver::new())
+        .map_err(ShaderLoadError::Preprocess)?;
+    let spv = compile_preprocessed_glsl(&preprocessed, stage).map_err(ShaderLoadError::Glsl)?;
+    let includes = preprocessed.files[1 ..].iter().map(PathBuf::from).collect();
+    Ok((device.create_shader_module(&spv), includes))
+}
+
+#[cfg(not(feature = "glsl"))]
+fn load_glsl(
+    _: &Device,
+    _: &Path,
+    _: ShaderStage,
+) -> Result<(ShaderModule, Vec<PathBuf>), ShaderLoadError> {
+    Err(ShaderLoadError::GlslNotEnabled)
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+fn modified_times(paths: Vec<PathBuf>) -> Vec<(PathBuf, Option<SystemTime>)> {
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = modified_time(&path);
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Owns pipelines built from shader files, and rebuilds them when the files change.
+///
+/// GLSL sources are preprocessed with a `FileResolver`, and changes to the files they
+/// include trigger a reload as well.
+///
+/// Pipelines are described by a closure that receives the shader modules in the order
+/// their paths were given. `poll` reloads changed files and swaps in rebuilt
+/// pipelines. When a shader fails to load, its previous module is kept and the
+/// pipelines using it are not rebuilt. When the closure returns an error, the previous
+/// pipeline is kept until its shaders change again.
+#[derive(Default)]
+pub struct ShaderWatcher {
+    shaders: Vec<WatchedShader>,
+    render_pipelines: Vec<WatchedPipeline<RenderPipeline>>,
+    compute_pipelines: Vec<WatchedPipeline<ComputePipeline>>,
+}
+
+impl ShaderWatcher {
+    pub fn new() -> Self {
+        ShaderWatcher::default()
+    }
+
+    fn add_shaders<P: AsRef<Path>>(
+        &mut self,
+        device: &Device,
+        paths: &[P],
+    ) -> Result<Vec<usize>, ShaderLoadError> {
+        paths
+            .iter()
+            .map(|path| {
+                let path = path.as_ref();
+                if let Some(index) = self.shaders.iter().position(|shader| shader.path == path) {
+                    return Ok(index);
+                }
+                let modified = modified_time(path);
+                let (module, includes) = load_shader(device, path)?;
+                let mut files = vec![(path.to_path_buf(), modified)];
+                files.extend(modified_times(includes));
+                self.shaders.push(WatchedShader {
+                    path: path.to_path_buf(),
+                    files,
+                    module,
+                });
+                Ok(self.shaders.len() - 1)
+            })
+            .collect()
+    }
+
+    fn modules(&self, shaders: &[usize]) -> Vec<&ShaderModule> {
+        shaders
+            .iter()
+            .map(|&index| &self.shaders[index].module)
+            .collect()
+    }
+
+    /// Load the shaders at `paths` and build a render pipeline from them.
+    pub fn add_render_pipeline<P, F, E>(
+        &mut self,
+        device: &Device,
+        paths: &[P],
+        build: F,
+    ) -> Result<RenderPipelineHandle, ShaderLoadError>
+    where
+        P: AsRef<Path>,
+        F: Fn(&Device, &[&ShaderModule]) -> Result<RenderPipeline, E> + 'static,
+        E: Into<Box<dyn Error>>,
+    {
+        let shaders = self.add_shaders(device, paths)?;
+        let modules = self.modules(&shaders);
+        let pipeline = WatchedPipeline::new(device, shaders, &modules, build)?;
+        self.render_pipelines.push(pipeline);
+        Ok(RenderPipelineHandle(self.render_pipelines.len() - 1))
+    }
+
+    /// Load the shader at `path` and build a compute pipeline from it.
+    pub fn add_compute_pipeline<P, F, E>(
+        &mut self,
+        device: &Device,
+        path: P,
+        build: F,
+    ) -> Result<ComputePipelineHandle, ShaderLoadError>
+    where
+        P: AsRef<Path>,
+        F: Fn(&Device, &[&ShaderModule]) -> Result<ComputePipeline, E> + 'static,
+        E: Into<Box<dyn Error>>,
+    {
+        let shaders = self.add_shaders(device, &[path])?;
+        let modules = self.modules(&shaders);
+        let pipeline = WatchedPipeline::new(device, shaders, &modules, build)?;
+        self.compute_pipelines.push(pipeline);
+        Ok(ComputePipelineHandle(self.compute_pipelines.len() - 1))
+    }
+
+    pub fn render_pipeline(&self, handle: RenderPipelineHandle) -> &RenderPipeline {
+        &self.render_pipelines[handle.0].pipeline
+    }
+
+    pub fn compute_pipeline(&self, handle: ComputePipelineHandle) -> &ComputePipeline {
+        &self.compute_pipelines[handle.0].pipeline
+    }
+
+    /// Reload shader files modified since the last poll and rebuild the pipelines using
+    /// them. Returns the files that failed to load or whose pipelines failed to build,
+    /// leaving those pipelines unchanged.
+    pub fn poll(&mut self, device: &Device) -> Vec<(PathBuf, ShaderLoadError)> {
+        let mut errors = Vec::new();
+        let mut status = vec![Status::Unchanged; self.shaders.len()];
+        for (shader, status) in self.shaders.iter_mut().zip(&mut status) {
+            let unchanged = shader
+                .files
+                .iter()
+                .all(|&(ref path, modified)| modified_time(path) == modified);
+            if unchanged {
+                continue;
+            }
+            let modified = modified_time(&shader.path);
+            for file in &mut shader.files {
+                file.1 = modified_time(&file.0);
+            }
+            match load_shader(device, &shader.path) {
+                Ok((module, includes)) => {
+                    shader.files = vec![(shader.path.clone(), modified)];
+                    shader.files.extend(modified_times(includes));
+                    shader.module = module;
+                    *status = Status::Reloaded;
+                }
+                Err(err) => {
+                    *status = Status::Failed;
+                    errors.push((shader.path.clone(), err));
+                }
+            }
+        }
+
+        for pipeline in &mut self.render_pipelines {
+            errors.extend(pipeline.rebuild(device, &self.shaders, &status));
+        }
+        for pipeline in &mut self.compute_pipelines {
+            errors.extend(pipeline.rebuild(device, &self.shaders, &status));
+        }
+        errors
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{InputStepMode, VertexAttributeDescriptor, VertexBufferDescriptor};
+
+use std::mem;
+
+/// Layout of a vertex struct in a vertex buffer.
+///
+/// With the "derive" feature, `#[derive(VertexLayout)]` implements this for `#[repr(C)]`
+/// structs. Each field becomes an attribute at the next shader location, with the
+/// format given by its type, such as `Float2` for `[f32; 2]` or `Char4` for `[i8; 4]`.
+/// Fields take options in a `#[vertex(...)]` attribute:
+///
+/// - `location = 3` sets the shader location, which following fields count up from,
+/// - `format = "Char4Norm"` sets the format, which has to match the size of the field,
+/// - `skip` leaves the field out of the attributes, still accounting for its size.
+///
+/// A `#[vertex(instance)]` attribute on the struct steps through it per instance.
+pub trait VertexLayout: Sized {
+    const STEP_MODE: InputStepMode;
+    const ATTRIBUTES: &'static [VertexAttributeDescriptor];
+
+    /// Describe a vertex buffer holding an array of `Self`.
+    fn buffer_descriptor() -> VertexBufferDescriptor<'static> {
+        VertexBufferDescriptor {
+            stride: mem::size_of::<Self>() as u32,
+            step_mode: Self::STEP_MODE,
+            attributes: Self::ATTRIBUTES,
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{
+    Device,
+    DisplayLabel,
+    Extent3d,
+    Texture,
+    TextureAspectFlags,
+    TextureDescriptor,
+    TextureDimension,
+    TextureFormat,
+    TextureFormatExt,
+    TextureUsageFlags,
+    TextureView,
+    TextureViewDescriptor,
+    TextureViewDimension,
+};
+
+use std::ops::Range;
+
+/// Faces of a cube map, in the order of their array layers.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CubeFace {
+    PositiveX = 0,
+    NegativeX = 1,
+    PositiveY = 2,
+    NegativeY = 3,
+    PositiveZ = 4,
+    NegativeZ = 5,
+}
+
+impl CubeFace {
+    pub const ALL: [CubeFace; 6] = [
+        CubeFace::PositiveX,
+        CubeFace::NegativeX,
+        CubeFace::PositiveY,
+        CubeFace::NegativeY,
+        CubeFace::PositiveZ,
+        CubeFace::NegativeZ,
+    ];
+}
+
+/// Builder for a `TextureView`, checking the view against the texture it is created from.
+///
+/// By default the view covers every mip level and array layer, with the format of the
+/// texture.
+pub struct TextureViewBuilder<'a> {
+    texture: &'a Texture,
+    label: Option<&'a str>,
+    format: TextureFormat,
+    dimension: TextureViewDimension,
+    aspect: TextureAspectFlags,
+    levels: Range<u32>,
+    layers: Range<u32>,
+}
+
+impl<'a> TextureViewBuilder<'a> {
+    pub fn label(mut self, label: &'a str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    pub fn format(mut self, format: TextureFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn dimension(mut self, dimension: TextureViewDimension) -> Self {
+        self.dimension = dimension;
+        self
+    }
+
+    pub fn aspect(mut self, aspect: TextureAspectFlags) -> Self {
+        self.aspect = aspect;
+        self
+    }
+
+    pub fn levels(mut self, levels: Range<u32>) -> Self {
+        self.levels = levels;
+        self
+    }
+
+    pub fn layers(mut self, layers: Range<u32>) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Check the view against the texture and return the descriptor that `build` creates
+    /// it with.
+    pub fn descriptor(&self) -> TextureViewDescriptor<'a> {
+        let texture = self.texture;
+        let label = DisplayLabel::of(&texture.label);
+        let layer_count = self.layers.end - self.layers.start;
+        assert!(
+            self.layers.start < self.layers.end && self.layers.end <= texture.array_size,
+            "Layers {:?} are out of range for texture {} with {} layers",
+            self.layers,
+            label,
+            texture.array_size
+        );
+        assert!(
+            self.levels.start < self.levels.end && self.levels.end <= texture.mip_level_count,
+            "Mip levels {:?} are out of range for texture {} with {} levels",
+            self.levels,
+            label,
+            texture.mip_level_count
+        );
+
+        let expected_dimension = match self.dimension {
+            TextureViewDimension::D1 => TextureDimension::D1,
+            TextureViewDimension::D3 => TextureDimension::D3,
+            _ => TextureDimension::D2,
+        };
+        assert_eq!(
+            texture.dimension, expected_dimension,
+            "View dimension {:?} does not match texture {}",
+            self.dimension, label
+        );
+        match self.dimension {
+            TextureViewDimension::D1 | TextureViewDimension::D2 | TextureViewDimension::D3 => {
+                assert_eq!(
+                    layer_count, 1,
+                    "{:?} views of texture {} cover a single layer",
+                    self.dimension, label
+                )
+            }
+            TextureViewDimension::D2Array => {}
+            TextureViewDimension::Cube | TextureViewDimension::CubeArray => {
+                assert_eq!(
+                    texture.size.width, texture.size.height,
+                    "Cube views need square textures, but texture {} is not",
+                    label
+                );
+                if self.dimension == TextureViewDimension::Cube {
+                    assert_eq!(
+                        layer_count, 6,
+                        "Cube views of texture {} cover exactly six layers",
+                        label
+                    );
+                } else {
+                    assert_eq!(
+                        layer_count % 6,
+                        0,
+                        "Cube array views of texture {} cover whole cubes",
+                        label
+                    );
+                }
+            }
+        }
+
+        TextureViewDescriptor {
+            label: self.label,
+            format: self.format,
+            dimension: self.dimension,
+            aspect: self.aspect,
+            base_mip_level: self.levels.start,
+            level_count: self.levels.end - self.levels.start,
+            base_array_layer: self.layers.start,
+            array_count: layer_count,
+        }
+    }
+
+    pub fn build(self) -> TextureView {
+        self.texture.create_view(&self.descriptor())
+    }
+}
+
+impl Texture {
+    /// Start building a view of this texture.
+    pub fn view_builder(&self) -> TextureViewBuilder<'_> {
+        let info = self.format.info();
+        let aspect = if info.is_color() {
+            TextureAspectFlags::COLOR
+        } else if info.stencil {
+            TextureAspectFlags::DEPTH | TextureAspectFlags::STENCIL
+        } else {
+            TextureAspectFlags::DEPTH
+        };
+        let dimension = match self.dimension {
+            TextureDimension::D1 => TextureViewDimension::D1,
+            TextureDimension::D2 if self.array_size > 1 => TextureViewDimension::D2Array,
+            TextureDimension::D2 => TextureViewDimension::D2,
+            TextureDimension::D3 => TextureViewDimension::D3,
+        };
+        TextureViewBuilder {
+            texture: self,
+            label: None,
+            format: self.format,
+            dimension,
+            aspect,
+            levels: 0 .. self.mip_level_count,
+            layers: 0 .. self.array_size,
+        }
+    }
+
+    /// Create a 2D view of a single array layer.
+    pub fn layer_view(&self, layer: u32) -> TextureView {
+        self.view_builder()
+            .dimension(TextureViewDimension::D2)
+            .layers(layer .. layer + 1)
+            .build()
+    }
+
+    /// Create a 2D view of one face of a cube map, for rendering into it.
+    pub fn face_view(&self, face: CubeFace) -> TextureView {
+        self.layer_view(face as u32)
+    }
+
+    /// Create a cube view over all six faces, for sampling.
+    pub fn cube_view(&self) -> TextureView {
+        self.view_builder()
+            .dimension(TextureViewDimension::Cube)
+            .layers(0 .. 6)
+            .build()
+    }
+}
+
+impl Device {
+    /// Create a cube map with square faces of `size` texels, stored as six array layers.
+    pub fn create_cube_texture(
+        &self,
+        size: u32,
+        format: TextureFormat,
+        usage: TextureUsageFlags,
+    ) -> Texture {
+        self.create_texture(&TextureDescriptor {
+            label: None,
+            size: Extent3d {
+                width: size,
+                height: size,
+                depth: 1,
+            },
+            array_size: 6,
+            dimension: TextureDimension::D2,
+            format,
+            usage,
+        })
+    }
+}
@@ -0,0 +1,14 @@
+#![allow(dead_code)]
+
+/// Create a device on the default adapter, without extensions.
+pub fn device() -> wgpu::Device {
+    let instance = wgpu::Instance::new();
+    let adapter = instance.get_adapter(&wgpu::AdapterDescriptor {
+        power_preference: wgpu::PowerPreference::Default,
+    });
+    adapter.create_device(&wgpu::DeviceDescriptor {
+        extensions: wgpu::Extensions {
+            anisotropic_filtering: false,
+        },
+    })
+}
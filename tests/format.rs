@@ -0,0 +1,27 @@
+use wgpu::{TextureFormat, TextureFormatExt, VertexFormat, VertexFormatExt};
+
+#[test]
+fn texture_format_info() {
+    let info = TextureFormat::Rgba8UnormSrgb.info();
+    assert_eq!(info.block_size, 4);
+    assert_eq!(info.block_dimensions, (1, 1));
+    assert_eq!(info.components, 4);
+    assert!(info.srgb && info.is_color() && !info.integer);
+
+    let info = TextureFormat::Rg32Uint.info();
+    assert_eq!(info.block_size, 8);
+    assert!(info.integer && !info.srgb);
+
+    let info = TextureFormat::D24UnormS8Uint.info();
+    assert!(info.depth && info.stencil && !info.is_color());
+    assert!(!TextureFormat::D32Float.info().stencil);
+}
+
+#[test]
+fn vertex_format_size() {
+    assert_eq!(VertexFormat::Char4.size(), 4);
+    assert_eq!(VertexFormat::Float2.size(), 8);
+    assert_eq!(VertexFormat::Float4.size(), 16);
+    assert_eq!(VertexFormat::Half4.size(), 8);
+    assert_eq!(VertexFormat::Int3.components(), 3);
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "glsl")]
+
+const FRAGMENT: &str = "#version 450
+
+layout(location = 0) out vec4 o_color;
+
+void main() {
+    o_color = vec4(COLOR);
+}
+";
+
+#[test]
+fn compile_with_defines() {
+    let spv =
+        wgpu::compile_glsl(FRAGMENT, wgpu::ShaderStage::Fragment, &[("COLOR", "1.0")]).unwrap();
+    assert_eq!(spv[0], 0x0723_0203);
+}
+
+#[test]
+fn diagnostics_point_at_source_lines() {
+    let err = wgpu::compile_glsl(FRAGMENT, wgpu::ShaderStage::Fragment, &[]).unwrap_err();
+    assert_eq!(err.diagnostics.len(), 1);
+    let diagnostic = &err.diagnostics[0];
+    assert_eq!(diagnostic.severity, wgpu::Severity::Error);
+    assert_eq!(diagnostic.file, 0);
+    assert_eq!(diagnostic.line, Some(6));
+    assert!(diagnostic.message.contains("COLOR"));
+}
+
+#[test]
+fn defines_keep_line_numbers() {
+    let source = FRAGMENT.replace("vec4(COLOR)", "vec4(COLOR, missing)");
+    let err = wgpu::compile_glsl(&source, wgpu::ShaderStage::Fragment, &[("COLOR", "1.0")])
+        .unwrap_err();
+    assert_eq!(err.diagnostics[0].line, Some(6));
+    assert!(err.diagnostics[0].message.contains("missing"));
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "image")]
+
+mod common;
+
+#[test]
+#[cfg(any(feature = "vulkan", feature = "metal", feature = "dx12"))]
+fn png_round_trip() {
+    let (width, height) = (5, 3);
+    let pixels = (0 .. width * height * 4)
+        .map(|i| (i * 7) as u8)
+        .collect::<Vec<_>>();
+    let image = image::DynamicImage::ImageRgba8(
+        image::RgbaImage::from_raw(width, height, pixels.clone()).unwrap(),
+    );
+
+    let mut device = common::device();
+
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    let texture = device.create_texture_from_image(
+        &mut encoder,
+        &image,
+        wgpu::TextureUsageFlags::TRANSFER_SRC,
+    );
+    device.get_queue().submit(&[encoder.finish()]);
+    assert_eq!(texture.format(), wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    let path = std::env::temp_dir().join("wgpu-png-round-trip.png");
+    texture.save_png(&mut device, &path).unwrap();
+    let saved = image::open(&path).unwrap().to_rgba();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(saved.dimensions(), (width, height));
+    assert_eq!(saved.into_raw(), pixels);
+}
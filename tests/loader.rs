@@ -0,0 +1,179 @@
+use wgpu::loader::{parse_dds, parse_ktx2, LoadError};
+use wgpu::{TextureDimension, TextureFormat};
+
+fn push_u32(data: &mut Vec<u8>, value: u32) {
+    data.extend_from_slice(&[
+        value as u8,
+        (value >> 8) as u8,
+        (value >> 16) as u8,
+        (value >> 24) as u8,
+    ]);
+}
+
+fn dds_header(width: u32, height: u32, mips: u32) -> Vec<u8> {
+    let mut data = b"DDS ".to_vec();
+    push_u32(&mut data, 124);
+    push_u32(&mut data, 0x1 | 0x2 | 0x4 | 0x1000 | 0x2_0000); // flags
+    push_u32(&mut data, height);
+    push_u32(&mut data, width);
+    push_u32(&mut data, 0); // pitch or linear size
+    push_u32(&mut data, 0); // depth
+    push_u32(&mut data, mips);
+    data.extend_from_slice(&[0; 11 * 4]);
+    push_u32(&mut data, 32); // pixel format size
+    push_u32(&mut data, 0x40); // DDPF_RGB
+    push_u32(&mut data, 0); // fourcc
+    push_u32(&mut data, 32); // bit count
+    push_u32(&mut data, 0x0000_00FF); // red mask
+    push_u32(&mut data, 0x0000_FF00); // green mask
+    push_u32(&mut data, 0x00FF_0000); // blue mask
+    push_u32(&mut data, 0xFF00_0000); // alpha mask
+    push_u32(&mut data, 0x1000); // caps
+    data.extend_from_slice(&[0; 4 * 4]); // caps2 to reserved
+    data
+}
+
+#[test]
+fn dds_rgba_mip_chain() {
+    let mut data = dds_header(4, 4, 3);
+    for (level, texels) in [16, 4, 1].iter().enumerate() {
+        data.resize(data.len() + texels * 4, level as u8);
+    }
+
+    let mut texture = parse_dds(&data).unwrap();
+    assert_eq!(texture.format, TextureFormat::Rgba8Unorm);
+    assert_eq!(texture.dimension, TextureDimension::D2);
+    assert_eq!(texture.mip_level_count, 3);
+    assert_eq!(texture.array_size, 1);
+    assert_eq!(texture.image(0, 0), &[0; 64][..]);
+    assert_eq!(texture.image(0, 2), &[2; 4][..]);
+
+    texture.truncate_mip_chain();
+    assert_eq!(texture.mip_level_count, 1);
+    assert_eq!(texture.image(0, 0), &[0; 64][..]);
+
+    data.pop();
+    assert_eq!(parse_dds(&data).unwrap_err(), LoadError::Truncated);
+}
+
+fn ktx2_file(header: [u32; 9], levels: &[(u32, u32)], payload: &[u8]) -> Vec<u8> {
+    let mut data = vec![
+        0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+    ];
+    for &value in header.iter() {
+        push_u32(&mut data, value);
+    }
+    data.extend_from_slice(&[0; 4 * 4 + 8 * 2]);
+    // Level offsets are relative to the start of the file, payload follows the index.
+    let payload_offset = data.len() as u32 + levels.len() as u32 * 3 * 8;
+    for &(offset, length) in levels {
+        for &value in [payload_offset + offset, 0, length, 0, length, 0].iter() {
+            push_u32(&mut data, value);
+        }
+    }
+    data.extend_from_slice(payload);
+    data
+}
+
+#[test]
+fn ktx2_array_layers() {
+    let header = [
+        37, // VK_FORMAT_R8G8B8A8_UNORM
+        1,  // type size
+        2,  // width
+        0,  // height
+        0,  // depth
+        3,  // layers
+        1,  // faces
+        1,  // levels
+        0,  // supercompression
+    ];
+    let mut payload = Vec::new();
+    for layer in 0 .. 3u8 {
+        payload.extend_from_slice(&[layer; 8]);
+    }
+    let data = ktx2_file(header, &[(0, 3 * 8)], &payload);
+
+    let texture = parse_ktx2(&data).unwrap();
+    assert_eq!(texture.format, TextureFormat::Rgba8Unorm);
+    assert_eq!(texture.dimension, TextureDimension::D1);
+    assert_eq!(texture.array_size, 3);
+    assert!(!texture.cube);
+    assert_eq!(texture.image(2, 0), &[2; 8][..]);
+
+    assert_eq!(parse_ktx2(b"DDS ").unwrap_err(), LoadError::InvalidMagic);
+}
+
+#[test]
+fn truncated_files() {
+    let mut dds = dds_header(4, 4, 3);
+    dds.extend_from_slice(&[0; (16 + 4 + 1) * 4]);
+    let ktx2 = ktx2_file([37, 1, 2, 2, 0, 0, 1, 1, 0], &[(0, 16)], &[0; 16]);
+    assert!(parse_dds(&dds).is_ok());
+    assert!(parse_ktx2(&ktx2).is_ok());
+
+    // Every prefix of a valid file has to be rejected without panicking.
+    for len in 0 .. dds.len() {
+        assert!(parse_dds(&dds[.. len]).is_err(), "DDS prefix of {} bytes", len);
+    }
+    for len in 0 .. ktx2.len() {
+        assert!(parse_ktx2(&ktx2[.. len]).is_err(), "KTX2 prefix of {} bytes", len);
+    }
+}
+
+#[test]
+fn oversized_dds_headers() {
+    for &(width, height, mips) in [
+        (u32::MAX, u32::MAX, 1),
+        (u32::MAX, 1, 32),
+        (1, 1, 33),
+        (1, 1, u32::MAX),
+        (0x1_0000, 0x1_0000, 17),
+    ]
+    .iter()
+    {
+        let mut data = dds_header(width, height, mips);
+        data.extend_from_slice(&[0; 64]);
+        assert!(
+            parse_dds(&data).is_err(),
+            "DDS of {}x{} with {} levels",
+            width,
+            height,
+            mips
+        );
+    }
+
+    // DX10 header with an array size that overflows once multiplied by six cube faces.
+    let mut data = dds_header(1, 1, 1);
+    let fourcc_offset = 4 + 4 * 20;
+    data[fourcc_offset - 4 .. fourcc_offset].copy_from_slice(&[0x4, 0, 0, 0]); // DDPF_FOURCC
+    data[fourcc_offset .. fourcc_offset + 4].copy_from_slice(b"DX10");
+    for &value in [28, 3, 0x4, u32::MAX, 0].iter() {
+        push_u32(&mut data, value);
+    }
+    assert_eq!(parse_dds(&data).unwrap_err(), LoadError::InvalidHeader);
+}
+
+#[test]
+fn oversized_ktx2_headers() {
+    let headers = [
+        // Image size overflows.
+        [37, 1, u32::MAX, u32::MAX, u32::MAX, 0, 1, 1, 0],
+        // More levels than any texture can have.
+        [37, 1, 1, 1, 0, 0, 1, u32::MAX, 0],
+        // Layer count overflows once multiplied by six cube faces.
+        [37, 1, 1, 1, 0, u32::MAX, 6, 1, 0],
+        // Layers that the level cannot hold.
+        [37, 1, 1, 1, 0, 0x4000_0000, 1, 1, 0],
+    ];
+    for header in headers.iter() {
+        let data = ktx2_file(*header, &[(0, 4)], &[0; 4]);
+        assert!(parse_ktx2(&data).is_err(), "KTX2 header {:?}", header);
+    }
+
+    // Level index pointing past the end of the address space.
+    let mut data = ktx2_file([37, 1, 1, 1, 0, 0, 1, 1, 0], &[(0, 4)], &[0; 4]);
+    let index = data.len() - 4 - 3 * 8;
+    data[index .. index + 16].copy_from_slice(&[0xFF; 16]);
+    assert_eq!(parse_ktx2(&data).unwrap_err(), LoadError::Truncated);
+}
@@ -0,0 +1,65 @@
+mod common;
+
+#[test]
+#[cfg(any(feature = "vulkan", feature = "metal", feature = "dx12"))]
+fn object_cache() {
+    use std::sync::Arc;
+
+    let mut device = common::device();
+    assert_eq!(device.object_cache_stats(), None);
+    device.enable_object_cache();
+
+    let nearest = device.create_sampler_cached(&wgpu::SamplerDescriptor::default());
+    let linear_desc = wgpu::SamplerDescriptor {
+        label: Some("linear"),
+        mag_filter: wgpu::FilterMode::Linear,
+        min_filter: wgpu::FilterMode::Linear,
+        ..Default::default()
+    };
+    let linear = device.create_sampler_cached(&linear_desc);
+    let linear_again = device.create_sampler_cached(&wgpu::SamplerDescriptor {
+        label: None,
+        ..linear_desc.clone()
+    });
+    assert!(!Arc::ptr_eq(&nearest, &linear));
+    assert!(Arc::ptr_eq(&linear, &linear_again));
+
+    let bindings = [wgpu::BindGroupLayoutBinding {
+        binding: 0,
+        visibility: wgpu::ShaderStageFlags::FRAGMENT,
+        ty: wgpu::BindingType::Sampler,
+    }];
+    let bind_group_layout =
+        device.create_bind_group_layout_cached(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            bindings: &bindings,
+        });
+    let create_bind_group = |sampler| {
+        device.create_bind_group_cached(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &bind_group_layout,
+            bindings: &[wgpu::Binding {
+                binding: 0,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            }],
+        })
+    };
+    let nearest_group = create_bind_group(&nearest);
+    let linear_group = create_bind_group(&linear);
+    let linear_group_again = create_bind_group(&linear_again);
+    assert!(!Arc::ptr_eq(&nearest_group, &linear_group));
+    assert!(Arc::ptr_eq(&linear_group, &linear_group_again));
+
+    assert_eq!(
+        device.object_cache_stats(),
+        Some(wgpu::ObjectCacheStats { hits: 2, misses: 5 })
+    );
+
+    // Objects are only shared while a handle to them is alive.
+    drop((linear, linear_again, linear_group, linear_group_again));
+    let _linear = device.create_sampler_cached(&linear_desc);
+    assert_eq!(
+        device.object_cache_stats(),
+        Some(wgpu::ObjectCacheStats { hits: 2, misses: 6 })
+    );
+}
@@ -0,0 +1,117 @@
+mod common;
+
+#[test]
+fn default_states() {
+    let rasterization = wgpu::default_rasterization_state();
+    assert_eq!(rasterization.front_face, wgpu::FrontFace::Ccw);
+    assert_eq!(rasterization.cull_mode, wgpu::CullMode::None);
+    assert_eq!(rasterization.depth_bias, 0);
+
+    let color = wgpu::default_color_state(wgpu::TextureFormat::Bgra8Unorm);
+    assert_eq!(color.format, wgpu::TextureFormat::Bgra8Unorm);
+    assert_eq!(color.color, wgpu::BlendDescriptor::REPLACE);
+    assert_eq!(color.alpha, wgpu::BlendDescriptor::REPLACE);
+    assert_eq!(color.write_mask, wgpu::ColorWriteFlags::ALL);
+
+    let depth_stencil = wgpu::default_depth_stencil_state(wgpu::TextureFormat::D32Float);
+    assert_eq!(depth_stencil.format, wgpu::TextureFormat::D32Float);
+    assert!(depth_stencil.depth_write_enabled);
+    assert_eq!(depth_stencil.depth_compare, wgpu::CompareFunction::Less);
+    assert_eq!(depth_stencil.stencil_front.compare, wgpu::CompareFunction::Always);
+    assert_eq!(depth_stencil.stencil_back.pass_op, wgpu::StencilOperation::Keep);
+    assert_eq!(depth_stencil.stencil_write_mask, 0);
+}
+
+#[test]
+#[cfg(any(feature = "vulkan", feature = "metal", feature = "dx12"))]
+fn builder_defaults() {
+    let device = common::device();
+
+    let vs_bytes = include_bytes!("cube.vert.spv");
+    let vs_module = device.create_shader_module(&wgpu::read_spirv(&vs_bytes[..]).unwrap());
+    let fs_bytes = include_bytes!("cube.frag.spv");
+    let fs_module = device.create_shader_module(&wgpu::read_spirv(&fs_bytes[..]).unwrap());
+    let layout = device
+        .create_pipeline_layout_from_shaders(&[&vs_module, &fs_module])
+        .unwrap();
+
+    let builder = wgpu::RenderPipelineBuilder::new(
+        &layout.pipeline_layout,
+        wgpu::PipelineStageDescriptor {
+            module: &vs_module,
+            entry_point: "main",
+            specialization: &[],
+        },
+    )
+    .fragment_stage(wgpu::PipelineStageDescriptor {
+        module: &fs_module,
+        entry_point: "main",
+        specialization: &[],
+    })
+    .cull_mode(wgpu::CullMode::Back)
+    .color_target(wgpu::TextureFormat::Bgra8Unorm)
+    .depth_test(wgpu::TextureFormat::D32Float, wgpu::CompareFunction::LessEqual);
+
+    let desc = builder.descriptor();
+    assert_eq!(desc.primitive_topology, wgpu::PrimitiveTopology::TriangleList);
+    assert_eq!(desc.rasterization_state.front_face, wgpu::FrontFace::Ccw);
+    assert_eq!(desc.rasterization_state.cull_mode, wgpu::CullMode::Back);
+    assert_eq!(desc.color_states.len(), 1);
+    assert_eq!(desc.color_states[0].format, wgpu::TextureFormat::Bgra8Unorm);
+    assert_eq!(desc.color_states[0].color, wgpu::BlendDescriptor::REPLACE);
+    let depth_stencil = desc.depth_stencil_state.as_ref().unwrap();
+    assert!(depth_stencil.depth_write_enabled);
+    assert_eq!(depth_stencil.depth_compare, wgpu::CompareFunction::LessEqual);
+    assert_eq!(desc.index_format, wgpu::IndexFormat::Uint16);
+    assert_eq!(desc.sample_count, 1);
+
+    let _pipeline = builder.build(&device);
+}
+
+#[test]
+#[cfg(any(feature = "vulkan", feature = "metal", feature = "dx12"))]
+fn depth_only_pipeline() {
+    let device = common::device();
+
+    let vs_bytes = include_bytes!("cube.vert.spv");
+    let vs_module = device.create_shader_module(&wgpu::read_spirv(&vs_bytes[..]).unwrap());
+    let layout = device
+        .create_pipeline_layout_from_shaders(&[&vs_module])
+        .unwrap();
+
+    let builder = wgpu::RenderPipelineBuilder::new(
+        &layout.pipeline_layout,
+        wgpu::PipelineStageDescriptor {
+            module: &vs_module,
+            entry_point: "main",
+            specialization: &[],
+        },
+    )
+    .depth_test(wgpu::TextureFormat::D32Float, wgpu::CompareFunction::Less);
+    assert!(builder.descriptor().fragment_stage.is_none());
+    let _pipeline = builder.build(&device);
+}
+
+#[test]
+#[should_panic(expected = "has color states but no fragment stage")]
+#[cfg(any(feature = "vulkan", feature = "metal", feature = "dx12"))]
+fn color_target_requires_fragment_stage() {
+    let device = common::device();
+
+    let vs_bytes = include_bytes!("cube.vert.spv");
+    let vs_module = device.create_shader_module(&wgpu::read_spirv(&vs_bytes[..]).unwrap());
+    let layout = device
+        .create_pipeline_layout_from_shaders(&[&vs_module])
+        .unwrap();
+
+    wgpu::RenderPipelineBuilder::new(
+        &layout.pipeline_layout,
+        wgpu::PipelineStageDescriptor {
+            module: &vs_module,
+            entry_point: "main",
+            specialization: &[],
+        },
+    )
+    .color_target(wgpu::TextureFormat::Bgra8Unorm)
+    .build(&device);
+}
@@ -0,0 +1,146 @@
+use std::io;
+
+const LIGHTING: &str = "#pragma once
+vec3 lighting(vec3 normal) {
+    return vec3(max(dot(normal, LIGHT_DIR), 0.0));
+}
+";
+
+const FORWARD: &str = "#version 450
+#include \"lighting.glsl\"
+#include \"lighting.glsl\"
+
+layout(location = 0) in vec3 v_normal;
+layout(location = 0) out vec4 o_color;
+
+void main() {
+    o_color = vec4(lighting(v_normal), 1.0);
+}
+";
+
+fn resolve(path: &str, _includer: &str) -> io::Result<wgpu::IncludedSource> {
+    let source = match path {
+        "lighting.glsl" => LIGHTING,
+        "broken.glsl" => "vec3 broken() {\n    return undefined;\n}\n",
+        "self.glsl" => "#include \"self.glsl\"\n",
+        "once.glsl" => "#pragma once\n#include \"once.glsl\"\nfloat once;\n",
+        _ => return Err(io::Error::new(io::ErrorKind::NotFound, "no such file")),
+    };
+    Ok(wgpu::IncludedSource {
+        name: format!("shaders/{}", path),
+        source: source.to_string(),
+    })
+}
+
+#[test]
+fn include_and_defines() {
+    let preprocessed = wgpu::preprocess(
+        "forward.frag",
+        FORWARD,
+        &[("LIGHT_DIR", "vec3(0.0, 0.0, 1.0)")],
+        &mut resolve,
+    )
+    .unwrap();
+    assert_eq!(preprocessed.files, ["forward.frag", "shaders/lighting.glsl"]);
+    assert_eq!(preprocessed.source.matches("vec3 lighting").count(), 1);
+    assert!(preprocessed
+        .source
+        .starts_with("#version 450\n#define LIGHT_DIR vec3(0.0, 0.0, 1.0)\n"));
+    assert!(preprocessed.source.contains("#line 1 1\n"));
+    assert!(preprocessed.source.contains("#line 3 0\n"));
+}
+
+#[test]
+fn include_errors() {
+    let source = "#version 450\n\n#include \"missing.glsl\"\n";
+    match wgpu::preprocess("main.frag", source, &[], &mut resolve) {
+        Err(wgpu::PreprocessError::Include { file, line, path, .. }) => {
+            assert_eq!((file.as_str(), line, path.as_str()), ("main.frag", 3, "missing.glsl"));
+        }
+        other => panic!("Unexpected result {:?}", other),
+    }
+
+    let source = "#include \"self.glsl\"\n";
+    match wgpu::preprocess("main.frag", source, &[], &mut resolve) {
+        Err(wgpu::PreprocessError::RecursiveInclude { file, line, .. }) => {
+            assert_eq!((file.as_str(), line), ("shaders/self.glsl", 1));
+        }
+        other => panic!("Unexpected result {:?}", other),
+    }
+
+    match wgpu::preprocess("main.frag", "#include lighting.glsl\n", &[], &mut resolve) {
+        Err(wgpu::PreprocessError::MalformedInclude { line: 1, .. }) => {}
+        other => panic!("Unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn pragma_once_before_recursion() {
+    let source = "#include \"once.glsl\"\n#include \"once.glsl\"\n";
+    let preprocessed = wgpu::preprocess("main.frag", source, &[], &mut resolve).unwrap();
+    assert_eq!(preprocessed.source.matches("float once;").count(), 1);
+}
+
+#[test]
+fn skipped_includes() {
+    // Expanding any of these would fail to resolve "missing.glsl".
+    let source = "#version 450
+// #include \"missing.glsl\"
+/* Block comment
+#include \"missing.glsl\"
+*/
+#if 0
+#include \"missing.glsl\"
+#elif defined(HAS_LIGHTING)
+#include \"lighting.glsl\"
+#else
+#include \"missing.glsl\"
+#endif
+#ifndef HAS_LIGHTING
+#if 1
+#include \"missing.glsl\"
+#endif
+#endif
+#define NO_SHADOWS
+#ifdef NO_SHADOWS
+#else
+#include \"missing.glsl\"
+#endif
+";
+    let preprocessed = wgpu::preprocess(
+        "main.frag",
+        source,
+        &[("HAS_LIGHTING", "1")],
+        &mut resolve,
+    )
+    .unwrap();
+    assert_eq!(preprocessed.files, ["main.frag", "shaders/lighting.glsl"]);
+    // Skipped lines are kept, so that line numbers still match.
+    assert_eq!(preprocessed.source.matches("#include \"missing.glsl\"").count(), 6);
+
+    // Unknown conditions are left to the compiler, so their includes are expanded.
+    let source = "#if defined(GL_ES) && 0\n#include \"missing.glsl\"\n#endif\n";
+    match wgpu::preprocess("main.frag", source, &[], &mut resolve) {
+        Err(wgpu::PreprocessError::Include { line: 2, .. }) => {}
+        other => panic!("Unexpected result {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "glsl")]
+fn diagnostics_point_at_included_file() {
+    let source = FORWARD.replace("lighting.glsl\"\n#include", "broken.glsl\"\n#include");
+    let preprocessed = wgpu::preprocess(
+        "forward.frag",
+        &source,
+        &[("LIGHT_DIR", "vec3(0.0, 0.0, 1.0)")],
+        &mut resolve,
+    )
+    .unwrap();
+    let err = wgpu::compile_preprocessed_glsl(&preprocessed, wgpu::ShaderStage::Fragment)
+        .unwrap_err();
+    let diagnostic = &err.diagnostics[0];
+    assert_eq!(diagnostic.file_name, Some("shaders/broken.glsl".to_string()));
+    assert_eq!(diagnostic.line, Some(2));
+    assert!(diagnostic.message.contains("undefined"));
+}
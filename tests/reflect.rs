@@ -0,0 +1,103 @@
+// `cube.vert.spv` and `cube.frag.spv` are compiled from the shaders of the cube example.
+
+fn spirv(bytes: &[u8]) -> Vec<u32> {
+    wgpu::read_spirv(bytes).unwrap()
+}
+
+#[test]
+fn reflect_vertex_inputs() {
+    let reflection = wgpu::reflect_spirv(&spirv(include_bytes!("cube.vert.spv"))).unwrap();
+    assert_eq!(reflection.stage, wgpu::ShaderStage::Vertex);
+    assert_eq!(reflection.entry_point, "main");
+    assert_eq!(
+        reflection.vertex_inputs,
+        [
+            wgpu::VertexInput {
+                location: 0,
+                format: wgpu::VertexFormat::Float4,
+            },
+            wgpu::VertexInput {
+                location: 1,
+                format: wgpu::VertexFormat::Float2,
+            },
+        ]
+    );
+
+    let layout = reflection.vertex_layout();
+    assert_eq!(layout.stride, 24);
+    assert_eq!(layout.attributes[1].offset, 16);
+    assert_eq!(layout.attributes[1].attribute_index, 1);
+}
+
+#[test]
+fn reflect_bindings() {
+    let vertex = wgpu::reflect_spirv(&spirv(include_bytes!("cube.vert.spv"))).unwrap();
+    let fragment = wgpu::reflect_spirv(&spirv(include_bytes!("cube.frag.spv"))).unwrap();
+    assert_eq!(
+        vertex.bindings,
+        [wgpu::ReflectedBinding {
+            set: 0,
+            binding: 0,
+            ty: wgpu::BindingType::UniformBuffer,
+        }]
+    );
+    assert!(fragment.vertex_inputs.is_empty());
+
+    let sets = wgpu::merge_bindings(&[vertex, fragment]).unwrap();
+    assert_eq!(sets.len(), 1);
+    let types = sets[0]
+        .iter()
+        .map(|b| (b.binding, b.visibility, b.ty))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        [
+            (0, wgpu::ShaderStageFlags::VERTEX, wgpu::BindingType::UniformBuffer),
+            (1, wgpu::ShaderStageFlags::FRAGMENT, wgpu::BindingType::SampledTexture),
+            (2, wgpu::ShaderStageFlags::FRAGMENT, wgpu::BindingType::Sampler),
+        ]
+    );
+}
+
+#[test]
+fn reflect_compute() {
+    let compute = spirv(include_bytes!("../examples/hello_compute/shader.comp.spv"));
+    let reflection = wgpu::reflect_spirv(&compute).unwrap();
+    assert_eq!(reflection.stage, wgpu::ShaderStage::Compute);
+    assert_eq!(reflection.local_size, Some([1, 1, 1]));
+    assert_eq!(reflection.bindings[0].ty, wgpu::BindingType::StorageBuffer);
+
+    // Storage images have no binding type in wgpu-native.
+    let reflection = wgpu::reflect_spirv(&spirv(include_bytes!("storage_texture.comp.spv")));
+    assert_eq!(
+        reflection.unwrap_err(),
+        wgpu::ReflectError::UnsupportedBinding { set: 0, binding: 0 }
+    );
+}
+
+#[test]
+fn conflicting_bindings() {
+    let compute = spirv(include_bytes!("../examples/hello_compute/shader.comp.spv"));
+    let compute = wgpu::reflect_spirv(&compute).unwrap();
+    let vertex = wgpu::reflect_spirv(&spirv(include_bytes!("cube.vert.spv"))).unwrap();
+    assert_eq!(
+        wgpu::merge_bindings(&[compute, vertex]).err(),
+        Some(wgpu::ReflectError::ConflictingBinding { set: 0, binding: 0 })
+    );
+}
+
+#[test]
+fn invalid_spirv() {
+    assert_eq!(
+        wgpu::reflect_spirv(&[0xDEAD_BEEF, 0, 0, 0, 0]),
+        Err(wgpu::ReflectError::InvalidMagic)
+    );
+
+    // The header is followed by an instruction claiming to be four words long.
+    let mut words = spirv(include_bytes!("cube.vert.spv"))[.. 5].to_vec();
+    words.push(4 << 16 | 15);
+    assert_eq!(
+        wgpu::reflect_spirv(&words),
+        Err(wgpu::ReflectError::Truncated)
+    );
+}
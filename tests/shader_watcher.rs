@@ -0,0 +1,96 @@
+mod common;
+
+#[test]
+#[cfg(all(feature = "glsl", any(feature = "vulkan", feature = "metal", feature = "dx12")))]
+fn shader_watcher() {
+    use filetime::{set_file_mtime, FileTime};
+    use std::cell::Cell;
+    use std::fs;
+    use std::path::Path;
+    use std::rc::Rc;
+
+    // Give every write a distinct modification time, whatever the file system's
+    // timestamp resolution.
+    let write = |path: &Path, source: &str, time: i64| {
+        fs::write(path, source).unwrap();
+        set_file_mtime(path, FileTime::from_unix_time(time, 0)).unwrap();
+    };
+
+    let dir = std::env::temp_dir().join(format!("wgpu-shader-watcher-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("shader.comp");
+    write(&path, "#version 450\nlayout(local_size_x = 1) in;\nvoid main() {}\n", 1_000_000);
+
+    let device = common::device();
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[],
+    });
+    let builds = Rc::new(Cell::new(0));
+    let builds_ref = Rc::clone(&builds);
+    let reject = Rc::new(Cell::new(false));
+    let reject_ref = Rc::clone(&reject);
+
+    let mut watcher = wgpu::util::ShaderWatcher::new();
+    let handle = watcher
+        .add_compute_pipeline(&device, &path, move |device, modules| {
+            if reject_ref.get() {
+                return Err("rejected");
+            }
+            builds_ref.set(builds_ref.get() + 1);
+            Ok(device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: &pipeline_layout,
+                compute_stage: wgpu::PipelineStageDescriptor {
+                    module: modules[0],
+                    entry_point: "main",
+                    specialization: &[],
+                },
+            }))
+        })
+        .unwrap();
+    // The debug output carries the pipeline id, telling whether it was swapped.
+    let pipeline = |watcher: &wgpu::util::ShaderWatcher| {
+        format!("{:?}", watcher.compute_pipeline(handle))
+    };
+    let first = pipeline(&watcher);
+    assert_eq!(builds.get(), 1);
+
+    // Nothing changed on disk.
+    assert!(watcher.poll(&device).is_empty());
+    assert_eq!(builds.get(), 1);
+    assert_eq!(pipeline(&watcher), first);
+
+    write(&path, "#version 450\nlayout(local_size_x = 2) in;\nvoid main() {}\n", 1_000_001);
+    assert!(watcher.poll(&device).is_empty());
+    assert_eq!(builds.get(), 2);
+    let second = pipeline(&watcher);
+    assert_ne!(second, first);
+
+    // The build closure fails, so the previous pipeline stays.
+    reject.set(true);
+    write(&path, "#version 450\nlayout(local_size_x = 4) in;\nvoid main() {}\n", 1_000_002);
+    let errors = watcher.poll(&device);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, path);
+    match errors[0].1 {
+        wgpu::util::ShaderLoadError::Pipeline(ref err) => assert_eq!(err.to_string(), "rejected"),
+        ref other => panic!("Unexpected error {:?}", other),
+    }
+    assert_eq!(builds.get(), 2);
+    assert_eq!(pipeline(&watcher), second);
+    reject.set(false);
+
+    write(&path, "#version 450\nvoid main() { oops }\n", 1_000_003);
+    let errors = watcher.poll(&device);
+    assert_eq!(errors.len(), 1);
+    match errors[0].1 {
+        wgpu::util::ShaderLoadError::Glsl(ref err) => assert_eq!(err.diagnostics[0].line, Some(2)),
+        ref other => panic!("Unexpected error {:?}", other),
+    }
+    assert_eq!(builds.get(), 2);
+    assert_eq!(pipeline(&watcher), second);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
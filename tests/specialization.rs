@@ -0,0 +1,92 @@
+mod common;
+
+#[test]
+#[cfg(any(feature = "vulkan", feature = "metal", feature = "dx12"))]
+fn specialization_constants() {
+    let values = [1.0f32, 2.0, 3.0];
+    let size = (values.len() * std::mem::size_of::<f32>()) as u32;
+
+    let mut device = common::device();
+
+    let cs_bytes = include_bytes!("specialization.comp.spv");
+    let cs_module = device.create_shader_module(&wgpu::read_spirv(&cs_bytes[..]).unwrap());
+
+    let staging_buffer = device
+        .create_buffer_mapped(
+            values.len(),
+            wgpu::BufferUsageFlags::MAP_READ
+                | wgpu::BufferUsageFlags::TRANSFER_DST
+                | wgpu::BufferUsageFlags::TRANSFER_SRC,
+        )
+        .fill_from_slice(&values);
+    let storage_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size,
+        usage: wgpu::BufferUsageFlags::STORAGE
+            | wgpu::BufferUsageFlags::TRANSFER_DST
+            | wgpu::BufferUsageFlags::TRANSFER_SRC,
+    });
+
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: None,
+        bindings: &[wgpu::BindGroupLayoutBinding {
+            binding: 0,
+            visibility: wgpu::ShaderStageFlags::COMPUTE,
+            ty: wgpu::BindingType::StorageBuffer,
+        }],
+    });
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &bind_group_layout,
+        bindings: &[wgpu::Binding {
+            binding: 0,
+            resource: wgpu::BindingResource::Buffer {
+                buffer: &storage_buffer,
+                range: 0 .. size,
+            },
+        }],
+    });
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[&bind_group_layout],
+    });
+    let compute_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: None,
+        layout: &pipeline_layout,
+        compute_stage: wgpu::PipelineStageDescriptor {
+            module: &cs_module,
+            entry_point: "main",
+            specialization: &[
+                wgpu::SpecializationConstant {
+                    id: 0,
+                    value: wgpu::SpecializationValue::Uint(10),
+                },
+                wgpu::SpecializationConstant {
+                    id: 1,
+                    value: wgpu::SpecializationValue::Float(0.5),
+                },
+                wgpu::SpecializationConstant {
+                    id: 2,
+                    value: wgpu::SpecializationValue::Bool(true),
+                },
+            ],
+        },
+    });
+
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    encoder.copy_buffer_to_buffer(&staging_buffer, 0, &storage_buffer, 0, size);
+    {
+        let mut cpass = encoder.begin_compute_pass();
+        cpass.set_pipeline(&compute_pipeline);
+        cpass.set_bind_group(0, &bind_group, &[]);
+        cpass.dispatch(values.len() as u32, 1, 1);
+    }
+    encoder.copy_buffer_to_buffer(&storage_buffer, 0, &staging_buffer, 0, size);
+    device.get_queue().submit(&[encoder.finish()]);
+
+    staging_buffer.map_read_async(0, size, |result: wgpu::BufferMapAsyncResult<&[f32]>| {
+        assert_eq!(result.unwrap().data, [-10.5, -20.5, -30.5]);
+    });
+    device.poll(true);
+}
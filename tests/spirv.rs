@@ -0,0 +1,172 @@
+mod common;
+
+use std::io::ErrorKind;
+
+const SPIRV: &[u8] = include_bytes!("../examples/hello_compute/shader.comp.spv");
+const SPECIALIZATION: &[u8] = include_bytes!("specialization.comp.spv");
+
+#[test]
+fn read_little_endian() {
+    let words = wgpu::read_spirv(SPIRV).unwrap();
+    assert_eq!(words.len() * 4, SPIRV.len());
+    assert_eq!(words[0], 0x0723_0203);
+}
+
+#[test]
+fn read_byte_swapped() {
+    let swapped = SPIRV
+        .chunks(4)
+        .flat_map(|word| word.iter().rev().cloned())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        wgpu::read_spirv(&swapped[..]).unwrap(),
+        wgpu::read_spirv(SPIRV).unwrap()
+    );
+}
+
+#[test]
+fn reject_truncated() {
+    let err = wgpu::read_spirv(&SPIRV[.. SPIRV.len() - 1]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn reject_invalid_magic() {
+    let err = wgpu::read_spirv(&b"#version 450"[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let err = wgpu::read_spirv(&[][..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn read_errors() {
+    let inner = |err: std::io::Error| {
+        *err.into_inner()
+            .unwrap()
+            .downcast::<wgpu::SpirvError>()
+            .unwrap()
+    };
+    assert_eq!(
+        inner(wgpu::read_spirv(&SPIRV[.. 18]).unwrap_err()),
+        wgpu::SpirvError::PartialWord
+    );
+    assert_eq!(
+        inner(wgpu::read_spirv(&SPIRV[.. 16]).unwrap_err()),
+        wgpu::SpirvError::TruncatedHeader
+    );
+}
+
+#[test]
+fn validate_header() {
+    let words = wgpu::read_spirv(SPIRV).unwrap();
+    assert_eq!(wgpu::validate_spirv(&words), Ok(()));
+    assert_eq!(
+        wgpu::validate_spirv(&words[.. 4]),
+        Err(wgpu::SpirvError::TruncatedHeader)
+    );
+    assert_eq!(
+        wgpu::validate_spirv(&[0x0302_2307, 0, 0, 0, 0]),
+        Err(wgpu::SpirvError::InvalidMagic(0x0302_2307))
+    );
+}
+
+/// Opcodes and operands of the specialization constants in a module, in order.
+fn spec_constants(words: &[u32]) -> Vec<(u32, Vec<u32>)> {
+    let mut constants = Vec::new();
+    let mut offset = 5;
+    while offset < words.len() {
+        let (opcode, word_count) = (words[offset] & 0xFFFF, (words[offset] >> 16) as usize);
+        if (48 ..= 50).contains(&opcode) {
+            constants.push((opcode, words[offset + 1 .. offset + word_count].to_vec()));
+        }
+        offset += word_count;
+    }
+    constants
+}
+
+#[test]
+fn specialize() {
+    let words = wgpu::read_spirv(SPECIALIZATION).unwrap();
+    let defaults = spec_constants(&words);
+    assert_eq!(
+        defaults.iter().map(|c| c.0).collect::<Vec<_>>(),
+        [50, 50, 49]
+    );
+
+    let specialized = wgpu::specialize_spirv(
+        &words,
+        &[
+            wgpu::SpecializationConstant {
+                id: 0,
+                value: wgpu::SpecializationValue::Uint(10),
+            },
+            wgpu::SpecializationConstant {
+                id: 1,
+                value: wgpu::SpecializationValue::Float(0.5),
+            },
+            wgpu::SpecializationConstant {
+                id: 2,
+                value: wgpu::SpecializationValue::Bool(true),
+            },
+            // Not declared by the shader.
+            wgpu::SpecializationConstant {
+                id: 7,
+                value: wgpu::SpecializationValue::Int(-1),
+            },
+        ],
+    );
+    assert_eq!(specialized.len(), words.len());
+    let constants = spec_constants(&specialized);
+    assert_eq!(constants[0], (50, vec![defaults[0].1[0], defaults[0].1[1], 10]));
+    assert_eq!(
+        constants[1],
+        (50, vec![defaults[1].1[0], defaults[1].1[1], 0.5f32.to_bits()])
+    );
+    assert_eq!(constants[2], (48, defaults[2].1.clone()));
+
+    assert_eq!(wgpu::specialize_spirv(&words, &[]), words);
+}
+
+#[test]
+#[should_panic(expected = "does not match the type declared in the shader")]
+fn specialize_wrong_type() {
+    let words = wgpu::read_spirv(SPECIALIZATION).unwrap();
+    wgpu::specialize_spirv(
+        &words,
+        &[wgpu::SpecializationConstant {
+            id: 1,
+            value: wgpu::SpecializationValue::Int(3),
+        }],
+    );
+}
+
+#[test]
+#[cfg(any(feature = "vulkan", feature = "metal", feature = "dx12"))]
+fn try_create_shader_module() {
+    let device = common::device();
+
+    let words = wgpu::read_spirv(SPIRV).unwrap();
+    let desc = |code| wgpu::ShaderModuleDescriptor { label: None, code };
+    assert!(device.try_create_shader_module(&desc(&words)).is_ok());
+    assert_eq!(
+        device.try_create_shader_module(&desc(&[0; 5])).unwrap_err(),
+        wgpu::SpirvError::InvalidMagic(0)
+    );
+}
+
+
+#[test]
+#[cfg(feature = "macros")]
+fn include_spirv() {
+    let words: &[u32] = wgpu::include_spirv!("tests/specialization.comp.spv");
+    assert_eq!(words, &wgpu::read_spirv(SPECIALIZATION).unwrap()[..]);
+}
+
+#[test]
+#[cfg(all(feature = "macros", feature = "glsl"))]
+fn include_glsl() {
+    let words: &[u32] = wgpu::include_glsl!("tests/specialization.comp");
+    assert_eq!(words[0], 0x0723_0203);
+    let reflection = wgpu::reflect_spirv(words).unwrap();
+    assert_eq!(reflection.stage, wgpu::ShaderStage::Compute);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+#[test]
+#[cfg(any(feature = "vulkan", feature = "metal", feature = "dx12"))]
+fn texture_readback() {
+    // An odd width forces the copy to pad every row.
+    let size = wgpu::Extent3d {
+        width: 3,
+        height: 2,
+        depth: 1,
+    };
+
+    let mut device = common::device();
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("readback"),
+        size,
+        array_size: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsageFlags::OUTPUT_ATTACHMENT | wgpu::TextureUsageFlags::TRANSFER_SRC,
+    });
+    assert_eq!(texture.label(), Some("readback"));
+    let view = texture.create_default_view();
+
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+            attachment: &view,
+            load_op: wgpu::LoadOp::Clear,
+            store_op: wgpu::StoreOp::Store,
+            clear_color: wgpu::Color {
+                r: 1.0,
+                g: 0.0,
+                b: 1.0,
+                a: 1.0,
+            },
+        }],
+        depth_stencil_attachment: None,
+    });
+    device.get_queue().submit(&[encoder.finish()]);
+
+    let texels = texture.read_to_vec(&mut device, 0, 0).unwrap();
+    assert_eq!(texels.len(), 3 * 2 * 4);
+    for texel in texels.chunks(4) {
+        assert_eq!(texel, [0xFF, 0, 0xFF, 0xFF]);
+    }
+
+    assert_eq!(
+        texture.read_to_vec(&mut device, 1, 0),
+        Err(wgpu::TextureReadError::InvalidLevel(1))
+    );
+    assert_eq!(
+        texture.read_to_vec(&mut device, 0, 1),
+        Err(wgpu::TextureReadError::InvalidSlice(1))
+    );
+}
@@ -0,0 +1,99 @@
+mod common;
+
+#[test]
+#[cfg(any(feature = "vulkan", feature = "metal", feature = "dx12"))]
+fn cube_view_descriptors() {
+    let device = common::device();
+
+    let texture = device.create_cube_texture(
+        4,
+        wgpu::TextureFormat::Rgba8Unorm,
+        wgpu::TextureUsageFlags::SAMPLED,
+    );
+
+    let default = texture.view_builder().descriptor();
+    assert_eq!(default.format, wgpu::TextureFormat::Rgba8Unorm);
+    assert_eq!(default.dimension, wgpu::TextureViewDimension::D2Array);
+    assert_eq!(default.aspect, wgpu::TextureAspectFlags::COLOR);
+    assert_eq!(
+        (default.base_mip_level, default.level_count),
+        (0, texture.mip_level_count())
+    );
+    assert_eq!((default.base_array_layer, default.array_count), (0, 6));
+
+    let cube = texture
+        .view_builder()
+        .dimension(wgpu::TextureViewDimension::Cube)
+        .descriptor();
+    assert_eq!(cube.dimension, wgpu::TextureViewDimension::Cube);
+    assert_eq!(cube.level_count, texture.mip_level_count());
+    assert_eq!((cube.base_array_layer, cube.array_count), (0, 6));
+
+    let face = texture
+        .view_builder()
+        .dimension(wgpu::TextureViewDimension::D2)
+        .layers(3 .. 4)
+        .descriptor();
+    assert_eq!(face.dimension, wgpu::TextureViewDimension::D2);
+    assert_eq!((face.base_array_layer, face.array_count), (3, 1));
+}
+
+#[test]
+#[should_panic(expected = "cover exactly six layers")]
+#[cfg(any(feature = "vulkan", feature = "metal", feature = "dx12"))]
+fn cube_view_of_partial_cube() {
+    let device = common::device();
+
+    let texture = device.create_cube_texture(
+        4,
+        wgpu::TextureFormat::Rgba8Unorm,
+        wgpu::TextureUsageFlags::SAMPLED,
+    );
+    texture
+        .view_builder()
+        .dimension(wgpu::TextureViewDimension::Cube)
+        .layers(0 .. 5)
+        .descriptor();
+}
+
+#[test]
+#[cfg(any(feature = "vulkan", feature = "metal", feature = "dx12"))]
+fn cube_face_readback() {
+    let mut device = common::device();
+
+    let texture = device.create_cube_texture(
+        2,
+        wgpu::TextureFormat::Rgba8Unorm,
+        wgpu::TextureUsageFlags::OUTPUT_ATTACHMENT | wgpu::TextureUsageFlags::TRANSFER_SRC,
+    );
+
+    // Clear every face to a different shade of red.
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    for &face in wgpu::CubeFace::ALL.iter() {
+        let view = texture.face_view(face);
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: &view,
+                load_op: wgpu::LoadOp::Clear,
+                store_op: wgpu::StoreOp::Store,
+                clear_color: wgpu::Color {
+                    r: face as u32 as f32 / 5.0,
+                    g: 0.0,
+                    b: 0.0,
+                    a: 1.0,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+    }
+    device.get_queue().submit(&[encoder.finish()]);
+
+    for &face in wgpu::CubeFace::ALL.iter() {
+        let texels = texture.read_to_vec(&mut device, 0, face as u32).unwrap();
+        let red = (face as u32 * 255 / 5) as u8;
+        for texel in texels.chunks(4) {
+            assert_eq!(texel, [red, 0, 0, 0xFF], "{:?} face", face);
+        }
+    }
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "derive")]
+
+use wgpu::{Uniform, UniformLayout};
+
+#[repr(C)]
+#[derive(Clone, Copy, Uniform)]
+struct Light {
+    pos: [f32; 3],
+    intensity: f32,
+    color: [f32; 3],
+    #[uniform(padding)]
+    _padding: u32,
+}
+
+/// Matches
+///
+/// ```glsl
+/// layout(std140) uniform Globals {
+///     mat4 proj;
+///     vec2 viewport;
+///     uint num_lights;
+///     float scales[2];
+///     Light lights[4];
+/// };
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Uniform)]
+struct Globals {
+    proj: [[f32; 4]; 4],
+    viewport: [f32; 2],
+    num_lights: u32,
+    #[uniform(padding)]
+    _padding: u32,
+    #[uniform(glsl = "float[2]")]
+    scales: [[f32; 4]; 2],
+    lights: [Light; 4],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Mat3x4([f32; 12]);
+
+#[repr(C)]
+#[derive(Clone, Copy, Uniform)]
+#[uniform(std430)]
+struct Particle {
+    pos: [f32; 2],
+    age: u32,
+    #[uniform(padding)]
+    _padding: u32,
+    velocity: [f32; 3],
+    mass: f32,
+    #[uniform(glsl = "mat3x4")]
+    transform: Mat3x4,
+    neighbours: [u32; 8],
+}
+
+#[test]
+fn std140_layout() {
+    assert_eq!(Light::LAYOUT, UniformLayout::Std140);
+    assert_eq!(Light::ALIGN, 16);
+    assert_eq!(Globals::ALIGN, 16);
+    assert_eq!(std::mem::size_of::<Globals>(), 64 + 16 + 32 + 4 * 32);
+}
+
+#[test]
+fn std430_layout() {
+    assert_eq!(Particle::LAYOUT, UniformLayout::Std430);
+    assert_eq!(Particle::ALIGN, 16);
+    assert_eq!(std::mem::size_of::<Particle>(), 112);
+}
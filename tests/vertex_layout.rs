@@ -0,0 +1,62 @@
+#![cfg(feature = "derive")]
+
+use wgpu::VertexLayout;
+
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
+struct Vertex {
+    pos: [i8; 4],
+    #[vertex(format = "Char4Norm")]
+    normal: [i8; 4],
+    tex_coord: [f32; 2],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
+#[vertex(instance)]
+struct Instance {
+    #[vertex(location = 4)]
+    offset: [f32; 3],
+    #[vertex(skip)]
+    _padding: u8,
+    scale: f32,
+    color: [u16; 4],
+}
+
+/// Location, format and offset of each attribute.
+fn attributes(desc: &wgpu::VertexBufferDescriptor) -> Vec<(u32, wgpu::VertexFormat, u32)> {
+    desc.attributes
+        .iter()
+        .map(|attribute| (attribute.attribute_index, attribute.format, attribute.offset))
+        .collect()
+}
+
+#[test]
+fn vertex_attributes() {
+    let desc = Vertex::buffer_descriptor();
+    assert_eq!(desc.stride, 16);
+    assert_eq!(desc.step_mode, wgpu::InputStepMode::Vertex);
+    assert_eq!(
+        attributes(&desc),
+        [
+            (0, wgpu::VertexFormat::Char4, 0),
+            (1, wgpu::VertexFormat::Char4Norm, 4),
+            (2, wgpu::VertexFormat::Float2, 8),
+        ]
+    );
+}
+
+#[test]
+fn instance_attributes() {
+    let desc = Instance::buffer_descriptor();
+    assert_eq!(desc.stride, std::mem::size_of::<Instance>() as u32);
+    assert_eq!(desc.step_mode, wgpu::InputStepMode::Instance);
+    assert_eq!(
+        attributes(&desc),
+        [
+            (4, wgpu::VertexFormat::Float3, 0),
+            (5, wgpu::VertexFormat::Float, 16),
+            (6, wgpu::VertexFormat::Ushort4, 20),
+        ]
+    );
+}
@@ -0,0 +1,168 @@
+//! Procedural macros for `wgpu`, re-exported by it with the "macros" and "derive"
+//! features.
+
+extern crate proc_macro;
+
+mod uniform;
+mod vertex;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, LitStr};
+use wgpu_shader::read_spirv;
+#[cfg(feature = "glsl")]
+use wgpu_shader::{preprocess, FileResolver, PreprocessedSource};
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Resolve a path relative to the directory of the crate invoking the macro.
+fn resolve_path(path: &LitStr) -> PathBuf {
+    let root = env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(root).join(path.value())
+}
+
+/// Replace the source string numbers of glslang messages like
+/// `ERROR: 1:12: 'foo' : undeclared identifier` with the names of the files.
+#[cfg(feature = "glsl")]
+fn remap_log(log: &str, source: &PreprocessedSource) -> String {
+    let mut output = String::new();
+    for line in log
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+    {
+        let mut parts = line.splitn(3, ':');
+        let remapped = match (parts.next(), parts.next(), parts.next()) {
+            (Some(severity), Some(file), Some(rest)) => file
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .and_then(|file| source.file_name(file))
+                .map(|name| format!("{}: {}:{}", severity, name, rest)),
+            _ => None,
+        };
+        output.push_str(&remapped.unwrap_or_else(|| line.to_string()));
+        output.push('\n');
+    }
+    output
+}
+
+fn expand_words(words: &[u32], dependencies: &[PathBuf]) -> TokenStream {
+    // Including the files makes the compiler rebuild the invoking crate when they change.
+    let dependencies = dependencies
+        .iter()
+        .map(|path| path.to_string_lossy().into_owned());
+    let expanded = quote! {
+        {
+            #( let _ = include_bytes!(#dependencies); )*
+            &[#(#words),*] as &'static [u32]
+        }
+    };
+    expanded.into()
+}
+
+fn error(span: Span, message: String) -> TokenStream {
+    syn::Error::new(span, message).to_compile_error().into()
+}
+
+#[cfg(feature = "glsl")]
+/// Compile a GLSL shader at build time, and expand to its SPIR-V words as a
+/// `&'static [u32]`, ready for `Device::create_shader_module`.
+///
+/// The path is relative to the root of the invoking crate, and its extension selects
+/// the shader stage: `.vert`, `.frag` or `.comp`. `#include` directives are resolved
+/// relative to the including file. Compiler diagnostics fail the build.
+#[cfg(feature = "glsl")]
+#[proc_macro]
+pub fn include_glsl(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+    let path = resolve_path(&literal);
+    let ty = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("vert") => glsl_to_spirv::ShaderType::Vertex,
+        Some("frag") => glsl_to_spirv::ShaderType::Fragment,
+        Some("comp") => glsl_to_spirv::ShaderType::Compute,
+        _ => {
+            return error(
+                literal.span(),
+                format!("{:?} does not end in .vert, .frag or .comp", path),
+            );
+        }
+    };
+
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(err) => return error(literal.span(), format!("cannot read {:?}: {}", path, err)),
+    };
+    let name = path.to_string_lossy();
+    let preprocessed = match preprocess(&name, &source, &[], &mut FileResolver::new()) {
+        Ok(preprocessed) => preprocessed,
+        Err(err) => return error(literal.span(), err.to_string()),
+    };
+
+    let output = match glsl_to_spirv::compile(&preprocessed.source, ty) {
+        Ok(output) => output,
+        Err(log) => {
+            return error(
+                literal.span(),
+                format!(
+                    "failed to compile {:?}:\n{}",
+                    path,
+                    remap_log(&log, &preprocessed)
+                ),
+            );
+        }
+    };
+    match read_spirv(output) {
+        Ok(words) => {
+            let dependencies = preprocessed
+                .files
+                .iter()
+                .map(PathBuf::from)
+                .collect::<Vec<_>>();
+            expand_words(&words, &dependencies)
+        }
+        Err(err) => error(literal.span(), err.to_string()),
+    }
+}
+
+/// Embed a SPIR-V module as a `&'static [u32]`, ready for `Device::create_shader_module`.
+///
+/// The path is relative to the root of the invoking crate. Unlike `include_bytes!`,
+/// the words are correctly aligned, and are checked to form a SPIR-V module.
+#[proc_macro]
+pub fn include_spirv(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+    let path = resolve_path(&literal);
+    let words = fs::File::open(&path)
+        .and_then(read_spirv)
+        .map_err(|err| format!("cannot read {:?}: {}", path, err));
+    match words {
+        Ok(words) => expand_words(&words, &[path]),
+        Err(err) => error(literal.span(), err),
+    }
+}
+
+/// Implement `wgpu::VertexLayout` for a `#[repr(C)]` struct.
+#[proc_macro_derive(VertexLayout, attributes(vertex))]
+pub fn derive_vertex_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    vertex::derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Implement `wgpu::Uniform` for a `#[repr(C)]` struct, checking its layout.
+///
+/// Arrays of two to four scalars are checked as vectors: a `[u32; 4]` field is a
+/// `uvec4`. Give fields declared as GLSL arrays, such as `uint[4]`, a
+/// `#[uniform(glsl = "uint[4]")]` attribute.
+#[proc_macro_derive(Uniform, attributes(uniform))]
+pub fn derive_uniform(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    uniform::derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
@@ -0,0 +1,300 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Error, Expr, Fields, Ident, Lit, Meta, NestedMeta, Type};
+
+use crate::vertex::{is_repr_c, scalar_name};
+
+/// GLSL type of a block member. Matrices are arrays of their column vectors.
+enum GlslType {
+    Scalar,
+    Vector(usize),
+    Array(Box<GlslType>, TokenStream),
+    Struct(Type),
+}
+
+impl GlslType {
+    fn from_rust(ty: &Type) -> Result<Self, Error> {
+        match *ty {
+            Type::Array(ref array) => {
+                let len = &array.len;
+                let elem = GlslType::from_rust(&array.elem)?;
+                let count = match array.len {
+                    Expr::Lit(ref lit) => match lit.lit {
+                        Lit::Int(ref int) => Some(int.value()),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                match (elem, count) {
+                    (GlslType::Scalar, Some(count)) if (2 ..= 4).contains(&count) => {
+                        Ok(GlslType::Vector(count as usize))
+                    }
+                    (elem, _) => Ok(GlslType::Array(Box::new(elem), quote!(#len))),
+                }
+            }
+            Type::Path(_) => match scalar_name(ty) {
+                Some(ref name) if name == "f32" || name == "i32" || name == "u32" => {
+                    Ok(GlslType::Scalar)
+                }
+                _ => Ok(GlslType::Struct(ty.clone())),
+            },
+            _ => Err(Error::new(
+                ty.span(),
+                "no GLSL type for this type, set one with #[uniform(glsl = \"...\")]",
+            )),
+        }
+    }
+
+    /// Parse a GLSL type name such as `vec3`, `mat4x3` or `vec4[2]`.
+    fn from_glsl(name: &str) -> Option<Self> {
+        let (base, mut suffix) = match name.find('[') {
+            Some(index) => name.split_at(index),
+            None => (name, ""),
+        };
+        let mut lengths = Vec::new();
+        while !suffix.is_empty() {
+            let end = suffix.find(']')?;
+            if !suffix.starts_with('[') {
+                return None;
+            }
+            lengths.push(suffix[1 .. end].trim().parse::<usize>().ok()?);
+            suffix = &suffix[end + 1 ..];
+        }
+
+        let component_count = |s: &str| match s.parse::<usize>() {
+            Ok(count) if (2 ..= 4).contains(&count) => Some(count),
+            _ => None,
+        };
+        let mut ty = match base.trim() {
+            "float" | "int" | "uint" => GlslType::Scalar,
+            base if base.starts_with("vec") => GlslType::Vector(component_count(&base[3 ..])?),
+            base if base.starts_with("ivec") || base.starts_with("uvec") => {
+                GlslType::Vector(component_count(&base[4 ..])?)
+            }
+            base if base.starts_with("mat") => {
+                let (columns, rows) = match base[3 ..].find('x') {
+                    Some(index) => (&base[3 .. 3 + index], &base[4 + index ..]),
+                    None => (&base[3 ..], &base[3 ..]),
+                };
+                let columns = component_count(columns)?;
+                GlslType::Array(
+                    Box::new(GlslType::Vector(component_count(rows)?)),
+                    quote!(#columns),
+                )
+            }
+            _ => return None,
+        };
+        // `float a[2][3]` is an array of two arrays of three floats.
+        for len in lengths.into_iter().rev() {
+            ty = GlslType::Array(Box::new(ty), quote!(#len));
+        }
+        Some(ty)
+    }
+
+    fn align(&self, std140: bool) -> TokenStream {
+        match *self {
+            GlslType::Scalar => quote!(4usize),
+            GlslType::Vector(2) => quote!(8usize),
+            GlslType::Vector(_) => quote!(16usize),
+            GlslType::Array(ref elem, _) => {
+                let align = elem.align(std140);
+                if std140 {
+                    quote!(::wgpu::__uniform_align_to(#align, 16))
+                } else {
+                    align
+                }
+            }
+            GlslType::Struct(ref ty) => quote!(<#ty as ::wgpu::Uniform>::ALIGN),
+        }
+    }
+
+    fn size(&self, std140: bool) -> TokenStream {
+        match *self {
+            GlslType::Scalar => quote!(4usize),
+            GlslType::Vector(count) => {
+                let size = 4 * count;
+                quote!(#size)
+            }
+            GlslType::Array(ref elem, ref len) => {
+                let size = elem.size(std140);
+                let align = self.align(std140);
+                quote!(::wgpu::__uniform_align_to(#size, #align) * (#len))
+            }
+            GlslType::Struct(ref ty) => quote!(::std::mem::size_of::<#ty>()),
+        }
+    }
+
+    /// Structs nested in this type, which need to share the layout of the outer one.
+    fn structs<'a>(&'a self, structs: &mut Vec<&'a Type>) {
+        match *self {
+            GlslType::Array(ref elem, _) => elem.structs(structs),
+            GlslType::Struct(ref ty) => structs.push(ty),
+            _ => {}
+        }
+    }
+}
+
+fn uniform_options(attrs: &[syn::Attribute]) -> Result<Vec<NestedMeta>, Error> {
+    let mut options = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("uniform")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => options.extend(list.nested),
+            meta => return Err(Error::new(meta.span(), "expected #[uniform(...)]")),
+        }
+    }
+    Ok(options)
+}
+
+/// GLSL type of a field, or `None` for padding.
+fn field_type(field: &syn::Field) -> Result<Option<GlslType>, Error> {
+    let mut glsl = None;
+    let mut padding = false;
+    for option in uniform_options(&field.attrs)? {
+        match option {
+            NestedMeta::Meta(Meta::Word(ref ident)) if ident == "padding" => padding = true,
+            NestedMeta::Meta(Meta::NameValue(ref pair)) if pair.ident == "glsl" => match pair.lit {
+                Lit::Str(ref name) => match GlslType::from_glsl(&name.value()) {
+                    Some(ty) => glsl = Some(ty),
+                    None => return Err(Error::new(name.span(), "unknown GLSL type")),
+                },
+                ref lit => return Err(Error::new(lit.span(), "expected a GLSL type name")),
+            },
+            ref option => {
+                return Err(Error::new(
+                    option.span(),
+                    "expected `glsl = \"...\"` or `padding`",
+                ));
+            }
+        }
+    }
+    if padding {
+        return Ok(None);
+    }
+    match glsl {
+        Some(ty) => Ok(Some(ty)),
+        None => GlslType::from_rust(&field.ty).map(Some),
+    }
+}
+
+fn is_std140(input: &DeriveInput) -> Result<bool, Error> {
+    let mut std140 = true;
+    for option in uniform_options(&input.attrs)? {
+        match option {
+            NestedMeta::Meta(Meta::Word(ref ident)) if ident == "std140" => std140 = true,
+            NestedMeta::Meta(Meta::Word(ref ident)) if ident == "std430" => std140 = false,
+            ref option => {
+                return Err(Error::new(option.span(), "expected `std140` or `std430`"));
+            }
+        }
+    }
+    Ok(std140)
+}
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => fields.named.iter().collect::<Vec<_>>(),
+            Fields::Unnamed(ref fields) => fields.unnamed.iter().collect::<Vec<_>>(),
+            Fields::Unit => Vec::new(),
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "Uniform can only be derived for structs",
+            ));
+        }
+    };
+    if !is_repr_c(&input) {
+        return Err(Error::new(
+            input.ident.span(),
+            "Uniform needs a #[repr(C)] struct",
+        ));
+    }
+    // The checks are constants, which cannot depend on type parameters.
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "Uniform cannot be derived for generic structs",
+        ));
+    }
+    let std140 = is_std140(&input)?;
+    let name = &input.ident;
+    let layout = if std140 {
+        quote!(::wgpu::UniformLayout::Std140)
+    } else {
+        quote!(::wgpu::UniformLayout::Std430)
+    };
+
+    let ident =
+        |prefix: &str, index: usize| Ident::new(&format!("{}{}", prefix, index), Span::call_site());
+    let mut statements = Vec::new();
+    let mut align = quote!(1usize);
+    let mut glsl_end = quote!(0usize);
+    for (index, field) in fields.iter().enumerate() {
+        let ty = &field.ty;
+        let rust_offset = ident("RUST_OFFSET_", index);
+        statements.push(if index == 0 {
+            quote!(const #rust_offset: usize = 0;)
+        } else {
+            let previous = ident("RUST_OFFSET_", index - 1);
+            let previous_ty = &fields[index - 1].ty;
+            quote! {
+                const #rust_offset: usize = ::wgpu::__uniform_align_to(
+                    #previous + ::std::mem::size_of::<#previous_ty>(),
+                    ::std::mem::align_of::<#ty>(),
+                );
+            }
+        });
+
+        let glsl = match field_type(field)? {
+            Some(glsl) => glsl,
+            None => continue,
+        };
+        let glsl_offset = ident("GLSL_OFFSET_", index);
+        let glsl_size = ident("GLSL_SIZE_", index);
+        let field_align = glsl.align(std140);
+        let field_size = glsl.size(std140);
+        statements.push(quote! {
+            const #glsl_offset: usize = ::wgpu::__uniform_align_to(#glsl_end, #field_align);
+            const #glsl_size: usize = #field_size;
+        });
+        // Fail to compile if the field is not where GLSL expects it, or of another size.
+        statements.push(quote_spanned! {ty.span()=>
+            let _: [(); #glsl_offset] = [(); #rust_offset];
+            let _: [(); #glsl_size] = [(); ::std::mem::size_of::<#ty>()];
+        });
+        let mut structs = Vec::new();
+        glsl.structs(&mut structs);
+        for nested in structs {
+            statements.push(quote_spanned! {ty.span()=>
+                let _: [(); #layout as usize] = [(); <#nested as ::wgpu::Uniform>::LAYOUT as usize];
+            });
+        }
+
+        align = quote!(::wgpu::__uniform_max(#align, #field_align));
+        glsl_end = quote!(#glsl_offset + #glsl_size);
+    }
+    if std140 {
+        align = quote!(::wgpu::__uniform_align_to(#align, 16));
+    }
+
+    Ok(quote! {
+        impl ::wgpu::Uniform for #name {
+            const LAYOUT: ::wgpu::UniformLayout = #layout;
+            const ALIGN: usize = #align;
+        }
+
+        impl #name {
+            #[allow(dead_code)]
+            fn __wgpu_uniform_layout_check() {
+                #(#statements)*
+                // Arrays of the struct need its size to be padded to its alignment.
+                let _: [(); ::wgpu::__uniform_align_to(
+                    #glsl_end,
+                    <#name as ::wgpu::Uniform>::ALIGN,
+                )] = [(); ::std::mem::size_of::<#name>()];
+            }
+        }
+    })
+}
@@ -0,0 +1,260 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Error, Expr, Fields, Lit, Meta, NestedMeta, Type};
+
+/// Vertex formats, with their size in bytes.
+const FORMATS: &[(&str, usize)] = &[
+    ("Uchar2", 2),
+    ("Uchar4", 4),
+    ("Char2", 2),
+    ("Char4", 4),
+    ("Uchar2Norm", 2),
+    ("Uchar4Norm", 4),
+    ("Char2Norm", 2),
+    ("Char4Norm", 4),
+    ("Ushort2", 4),
+    ("Ushort4", 8),
+    ("Short2", 4),
+    ("Short4", 8),
+    ("Ushort2Norm", 4),
+    ("Ushort4Norm", 8),
+    ("Short2Norm", 4),
+    ("Short4Norm", 8),
+    ("Half2", 4),
+    ("Half4", 8),
+    ("Float", 4),
+    ("Float2", 8),
+    ("Float3", 12),
+    ("Float4", 16),
+    ("Uint", 4),
+    ("Uint2", 8),
+    ("Uint3", 12),
+    ("Uint4", 16),
+    ("Int", 4),
+    ("Int2", 8),
+    ("Int3", 12),
+    ("Int4", 16),
+];
+
+/// Name of the scalar type, if `ty` is a plain identifier.
+pub(crate) fn scalar_name(ty: &Type) -> Option<String> {
+    match *ty {
+        Type::Path(ref path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.value().ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Default format of a field, from scalars and arrays of them.
+fn format_of(ty: &Type) -> Option<String> {
+    let (scalar, count) = match *ty {
+        Type::Array(ref array) => {
+            let count = match array.len {
+                Expr::Lit(ref lit) => match lit.lit {
+                    Lit::Int(ref int) => int.value(),
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            (scalar_name(&array.elem)?, count)
+        }
+        _ => (scalar_name(ty)?, 1),
+    };
+    let base = match scalar.as_str() {
+        "f32" => "Float",
+        "u32" => "Uint",
+        "i32" => "Int",
+        "u16" => "Ushort",
+        "i16" => "Short",
+        "u8" => "Uchar",
+        "i8" => "Char",
+        _ => return None,
+    };
+    let format = match count {
+        1 => base.to_string(),
+        _ => format!("{}{}", base, count),
+    };
+    FORMATS
+        .iter()
+        .find(|&&(name, _)| name == format)
+        .map(|&(name, _)| name.to_string())
+}
+
+struct FieldOptions {
+    location: Option<u32>,
+    format: Option<(String, proc_macro2::Span)>,
+    skip: bool,
+}
+
+fn vertex_options(attrs: &[syn::Attribute]) -> Result<Vec<NestedMeta>, Error> {
+    let mut options = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("vertex")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => options.extend(list.nested),
+            meta => return Err(Error::new(meta.span(), "expected #[vertex(...)]")),
+        }
+    }
+    Ok(options)
+}
+
+fn field_options(field: &syn::Field) -> Result<FieldOptions, Error> {
+    let mut options = FieldOptions {
+        location: None,
+        format: None,
+        skip: false,
+    };
+    for option in vertex_options(&field.attrs)? {
+        match option {
+            NestedMeta::Meta(Meta::Word(ref ident)) if ident == "skip" => options.skip = true,
+            NestedMeta::Meta(Meta::NameValue(ref pair)) if pair.ident == "location" => {
+                match pair.lit {
+                    Lit::Int(ref int) => options.location = Some(int.value() as u32),
+                    ref lit => return Err(Error::new(lit.span(), "expected a location number")),
+                }
+            }
+            NestedMeta::Meta(Meta::NameValue(ref pair)) if pair.ident == "format" => {
+                match pair.lit {
+                    Lit::Str(ref name) => options.format = Some((name.value(), name.span())),
+                    ref lit => return Err(Error::new(lit.span(), "expected a format name")),
+                }
+            }
+            ref option => {
+                return Err(Error::new(
+                    option.span(),
+                    "expected `location = N`, `format = \"...\"` or `skip`",
+                ));
+            }
+        }
+    }
+    Ok(options)
+}
+
+fn step_mode(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let mut instance = false;
+    for option in vertex_options(&input.attrs)? {
+        match option {
+            NestedMeta::Meta(Meta::Word(ref ident)) if ident == "instance" => instance = true,
+            ref option => return Err(Error::new(option.span(), "expected `instance`")),
+        }
+    }
+    Ok(if instance {
+        quote!(::wgpu::InputStepMode::Instance)
+    } else {
+        quote!(::wgpu::InputStepMode::Vertex)
+    })
+}
+
+pub(crate) fn is_repr_c(input: &DeriveInput) -> bool {
+    input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| attr.parse_meta().ok())
+        .any(|meta| match meta {
+            Meta::List(list) => list.nested.iter().any(|nested| match *nested {
+                NestedMeta::Meta(Meta::Word(ref ident)) => ident == "C",
+                _ => false,
+            }),
+            _ => false,
+        })
+}
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => fields.named.iter().collect::<Vec<_>>(),
+            Fields::Unnamed(ref fields) => fields.unnamed.iter().collect::<Vec<_>>(),
+            Fields::Unit => Vec::new(),
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "VertexLayout can only be derived for structs",
+            ));
+        }
+    };
+    // Field offsets are computed with the layout rules of `repr(C)`.
+    if !is_repr_c(&input) {
+        return Err(Error::new(
+            input.ident.span(),
+            "VertexLayout needs a #[repr(C)] struct",
+        ));
+    }
+    let step_mode = step_mode(&input)?;
+
+    let mut statements = Vec::new();
+    let mut attributes = Vec::new();
+    let mut location = 0;
+    for (index, field) in fields.iter().enumerate() {
+        let ty = &field.ty;
+        let offset = syn::Ident::new(&format!("_offset{}", index), proc_macro2::Span::call_site());
+        statements.push(if index == 0 {
+            quote!(let #offset = 0usize;)
+        } else {
+            let previous = syn::Ident::new(
+                &format!("_offset{}", index - 1),
+                proc_macro2::Span::call_site(),
+            );
+            let previous_ty = &fields[index - 1].ty;
+            quote! {
+                let #offset = {
+                    let end = #previous + ::std::mem::size_of::<#previous_ty>();
+                    let align = ::std::mem::align_of::<#ty>();
+                    (end + align - 1) & !(align - 1)
+                };
+            }
+        });
+
+        let options = field_options(field)?;
+        if options.skip {
+            continue;
+        }
+        let (format, span) = match options.format {
+            Some((format, span)) => (format, span),
+            None => match format_of(ty) {
+                Some(format) => (format, ty.span()),
+                None => {
+                    return Err(Error::new(
+                        ty.span(),
+                        "no vertex format for this type, set one with #[vertex(format = \"...\")]",
+                    ));
+                }
+            },
+        };
+        let size = match FORMATS.iter().find(|&&(name, _)| name == format) {
+            Some(&(_, size)) => size,
+            None => return Err(Error::new(span, "unknown vertex format")),
+        };
+        // Fails to compile if the field does not have the size of its format.
+        statements.push(quote_spanned! {ty.span()=>
+            let _: [(); #size] = [(); ::std::mem::size_of::<#ty>()];
+        });
+
+        location = options.location.unwrap_or(location);
+        let format = syn::Ident::new(&format, span);
+        attributes.push(quote! {
+            ::wgpu::VertexAttributeDescriptor {
+                attribute_index: #location,
+                format: ::wgpu::VertexFormat::#format,
+                offset: #offset as u32,
+            }
+        });
+        location += 1;
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::wgpu::VertexLayout for #name #ty_generics #where_clause {
+            const STEP_MODE: ::wgpu::InputStepMode = #step_mode;
+            const ATTRIBUTES: &'static [::wgpu::VertexAttributeDescriptor] = {
+                #(#statements)*
+                &[#(#attributes),*]
+            };
+        }
+    })
+}
@@ -0,0 +1,18 @@
+//! Shader preprocessing and SPIR-V loading for `wgpu`, which re-exports all of it.
+//!
+//! These live in their own crate so that the build time macros of `wgpu-macros`
+//! share them with the runtime shader loading of `wgpu`.
+
+mod preprocess;
+mod spirv;
+
+pub use crate::preprocess::{
+    inject_defines,
+    preprocess,
+    FileResolver,
+    IncludeResolver,
+    IncludedSource,
+    PreprocessError,
+    PreprocessedSource,
+};
+pub use crate::spirv::{read_spirv, validate_spirv, SpirvError, HEADER_WORDS, SPIRV_MAGIC};
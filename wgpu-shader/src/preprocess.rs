@@ -0,0 +1,478 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Source of a file pulled in by an `#include` directive.
+#[derive(Clone, Debug)]
+pub struct IncludedSource {
+    /// Name identifying the file, used in diagnostics and to detect repeated includes.
+    pub name: String,
+    pub source: String,
+}
+
+/// Looks up the files named by `#include` directives.
+pub trait IncludeResolver {
+    /// Resolve `path`, as written in an `#include` of the file named `includer`.
+    fn resolve(&mut self, path: &str, includer: &str) -> io::Result<IncludedSource>;
+}
+
+impl<F> IncludeResolver for F
+where
+    F: FnMut(&str, &str) -> io::Result<IncludedSource>,
+{
+    fn resolve(&mut self, path: &str, includer: &str) -> io::Result<IncludedSource> {
+        self(path, includer)
+    }
+}
+
+/// Resolves includes from the file system, relative to the including file first and
+/// then to each of the include directories in order.
+#[derive(Clone, Debug, Default)]
+pub struct FileResolver {
+    include_dirs: Vec<PathBuf>,
+}
+
+impl FileResolver {
+    pub fn new() -> Self {
+        FileResolver::default()
+    }
+
+    pub fn include_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.include_dirs.push(dir.into());
+        self
+    }
+}
+
+/// Remove `.` and `..` components, so that a file has one name however it is reached.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                _ => normalized.push(component),
+            },
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+impl IncludeResolver for FileResolver {
+    fn resolve(&mut self, path: &str, includer: &str) -> io::Result<IncludedSource> {
+        let relative = Path::new(includer).parent().map(|dir| dir.join(path));
+        let candidates = relative
+            .into_iter()
+            .chain(self.include_dirs.iter().map(|dir| dir.join(path)));
+        for candidate in candidates {
+            if candidate.is_file() {
+                let candidate = normalize(&candidate);
+                return Ok(IncludedSource {
+                    source: fs::read_to_string(&candidate)?,
+                    name: candidate.to_string_lossy().into_owned(),
+                });
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{:?} not found", path),
+        ))
+    }
+}
+
+#[derive(Debug)]
+pub enum PreprocessError {
+    /// An `#include` directive is not followed by a quoted or bracketed path.
+    MalformedInclude { file: String, line: u32 },
+    /// The resolver failed to provide an included file.
+    Include {
+        file: String,
+        line: u32,
+        path: String,
+        error: io::Error,
+    },
+    /// A file includes itself, directly or through other files.
+    RecursiveInclude {
+        file: String,
+        line: u32,
+        path: String,
+    },
+}
+
+impl fmt::Display for PreprocessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PreprocessError::MalformedInclude { ref file, line } => {
+                write!(f, "{}:{}: malformed #include directive", file, line)
+            }
+            PreprocessError::Include {
+                ref file,
+                line,
+                ref path,
+                ref error,
+            } => write!(f, "{}:{}: cannot include {:?}: {}", file, line, path, error),
+            PreprocessError::RecursiveInclude {
+                ref file,
+                line,
+                ref path,
+            } => write!(f, "{}:{}: {:?} includes itself", file, line, path),
+        }
+    }
+}
+
+impl Error for PreprocessError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            PreprocessError::Include { ref error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Shader source with includes expanded, ready to be compiled.
+#[derive(Clone, Debug)]
+pub struct PreprocessedSource {
+    pub source: String,
+    /// Names of the files making up the source, indexed by the source string number
+    /// of the `#line` directives. The main file comes first.
+    pub files: Vec<String>,
+}
+
+impl PreprocessedSource {
+    /// Name of the file with the given source string number, as reported in compiler
+    /// diagnostics.
+    pub fn file_name(&self, file: u32) -> Option<&str> {
+        self.files.get(file as usize).map(String::as_str)
+    }
+}
+
+/// Parse the path of an `#include` directive, if `line` is one.
+fn include_path(line: &str) -> Option<Option<&str>> {
+    let line = line.trim_start();
+    if !line.starts_with('#') {
+        return None;
+    }
+    let directive = line[1 ..].trim_start();
+    if !directive.starts_with("include") {
+        return None;
+    }
+    let rest = &directive["include".len() ..];
+    if rest.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    let rest = rest.trim();
+    let path = match rest.chars().next() {
+        Some('"') => rest[1 ..].find('"').map(|end| &rest[1 .. end + 1]),
+        Some('<') => rest[1 ..].find('>').map(|end| &rest[1 .. end + 1]),
+        _ => None,
+    };
+    Some(path)
+}
+
+fn is_pragma_once(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with('#')
+        && line[1 ..]
+            .split_whitespace()
+            .eq(["pragma", "once"].iter().cloned())
+}
+
+/// Split a directive line into the directive name and the rest of the line, without
+/// any trailing comment.
+fn directive(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_start();
+    if !line.starts_with('#') {
+        return None;
+    }
+    let line = line[1 ..].trim_start();
+    let end = line
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(line.len());
+    let rest = &line[end ..];
+    let rest = rest.find("//").map_or(rest, |comment| &rest[.. comment]);
+    let rest = rest.find("/*").map_or(rest, |comment| &rest[.. comment]);
+    Some((&line[.. end], rest.trim()))
+}
+
+/// Whether a line that starts inside a block comment if `in_comment` is set, ends
+/// inside one.
+fn ends_in_comment(line: &str, mut in_comment: bool) -> bool {
+    let mut in_string = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_comment {
+            if c == '*' && chars.peek() == Some(&'/') {
+                chars.next();
+                in_comment = false;
+            }
+        } else if in_string {
+            in_string = c != '"';
+        } else {
+            match (c, chars.peek()) {
+                ('"', _) => in_string = true,
+                ('/', Some(&'/')) => break,
+                ('/', Some(&'*')) => {
+                    chars.next();
+                    in_comment = true;
+                }
+                _ => {}
+            }
+        }
+    }
+    in_comment
+}
+
+/// A branch of an `#if` group. Conditions are only evaluated when they are integer
+/// literals or test macros whose state is known from the source or the given defines,
+/// and other branches are treated as active.
+#[derive(Clone, Copy)]
+struct Branch {
+    /// Whether this branch is taken, if known.
+    taken: Option<bool>,
+    /// Whether an earlier branch of the group was taken, if known.
+    done: Option<bool>,
+}
+
+impl Branch {
+    fn new(taken: Option<bool>) -> Self {
+        Branch { taken, done: taken }
+    }
+
+    fn elif(self, condition: Option<bool>) -> Self {
+        match self.done {
+            Some(true) => Branch::new(Some(false)),
+            Some(false) => Branch::new(condition),
+            None => Branch {
+                taken: condition.filter(|&taken| !taken),
+                done: None,
+            },
+        }
+    }
+
+    fn otherwise(self) -> Self {
+        Branch::new(self.done.map(|done| !done))
+    }
+}
+
+struct Expander<'r, R: ?Sized> {
+    resolver: &'r mut R,
+    files: Vec<String>,
+    stack: Vec<usize>,
+    once: HashSet<usize>,
+    /// Macros known to be defined or undefined.
+    macros: HashMap<String, bool>,
+    output: String,
+}
+
+impl<'r, R: IncludeResolver + ?Sized> Expander<'r, R> {
+    /// Evaluate an `#if` or `#elif` condition, if possible.
+    fn condition(&self, condition: &str) -> Option<bool> {
+        if let Ok(value) = condition.parse::<i64>() {
+            return Some(value != 0);
+        }
+        let negate = condition.starts_with('!');
+        let condition = if negate {
+            condition[1 ..].trim_start()
+        } else {
+            condition
+        };
+        if !condition.starts_with("defined") {
+            return None;
+        }
+        let name = condition["defined".len() ..].trim();
+        let name = match (name.starts_with('('), name.ends_with(')')) {
+            (true, true) => name[1 .. name.len() - 1].trim(),
+            (false, false) => name,
+            _ => return None,
+        };
+        self.macros.get(name).map(|&defined| defined != negate)
+    }
+
+    /// Track conditional groups and macro definitions.
+    fn directive(&mut self, branches: &mut Vec<Branch>, name: &str, rest: &str) {
+        let inactive = branches.iter().any(|branch| branch.taken == Some(false));
+        match name {
+            // Nested groups of an inactive branch are inactive as a whole.
+            "if" | "ifdef" | "ifndef" if inactive => branches.push(Branch {
+                taken: Some(false),
+                done: Some(true),
+            }),
+            "if" => branches.push(Branch::new(self.condition(rest))),
+            "ifdef" => branches.push(Branch::new(self.macros.get(rest).cloned())),
+            "ifndef" => branches.push(Branch::new(self.macros.get(rest).map(|defined| !defined))),
+            "elif" => {
+                let condition = self.condition(rest);
+                if let Some(branch) = branches.last_mut() {
+                    *branch = branch.elif(condition);
+                }
+            }
+            "else" => {
+                if let Some(branch) = branches.last_mut() {
+                    *branch = branch.otherwise();
+                }
+            }
+            "endif" => {
+                branches.pop();
+            }
+            "define" | "undef" if !inactive => {
+                let end = rest
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                let macro_name = rest[.. end].to_string();
+                // Definitions in branches of unknown state leave the macro unknown.
+                if branches.iter().all(|branch| branch.taken == Some(true)) {
+                    self.macros.insert(macro_name, name == "define");
+                } else {
+                    self.macros.remove(&macro_name);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn expand(&mut self, file: usize, source: &str) -> Result<(), PreprocessError> {
+        self.stack.push(file);
+        let mut in_comment = false;
+        let mut branches = Vec::new();
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index as u32 + 1;
+            let commented = in_comment;
+            in_comment = ends_in_comment(line, in_comment);
+            if !commented {
+                if let Some((name, rest)) = directive(line) {
+                    self.directive(&mut branches, name, rest);
+                }
+            }
+            // Directives in comments and inactive branches are left to the compiler,
+            // which ignores them.
+            let inactive = branches.iter().any(|branch| branch.taken == Some(false));
+            if commented || inactive {
+                self.output.push_str(line);
+                self.output.push('\n');
+                continue;
+            }
+            if is_pragma_once(line) {
+                self.once.insert(file);
+                self.output.push('\n');
+                continue;
+            }
+            let path = match include_path(line) {
+                Some(Some(path)) => path,
+                Some(None) => {
+                    return Err(PreprocessError::MalformedInclude {
+                        file: self.files[file].clone(),
+                        line: line_number,
+                    });
+                }
+                None => {
+                    self.output.push_str(line);
+                    self.output.push('\n');
+                    continue;
+                }
+            };
+
+            let included = self
+                .resolver
+                .resolve(path, &self.files[file])
+                .map_err(|error| PreprocessError::Include {
+                    file: self.files[file].clone(),
+                    line: line_number,
+                    path: path.to_string(),
+                    error,
+                })?;
+            let included_file = match self.files.iter().position(|name| *name == included.name) {
+                Some(index) => index,
+                None => {
+                    self.files.push(included.name);
+                    self.files.len() - 1
+                }
+            };
+            if self.once.contains(&included_file) {
+                self.output.push('\n');
+                continue;
+            }
+            if self.stack.contains(&included_file) {
+                return Err(PreprocessError::RecursiveInclude {
+                    file: self.files[file].clone(),
+                    line: line_number,
+                    path: path.to_string(),
+                });
+            }
+            self.output
+                .push_str(&format!("#line 1 {}\n", included_file));
+            self.expand(included_file, &included.source)?;
+            self.output
+                .push_str(&format!("#line {} {}\n", line_number + 1, file));
+        }
+        self.stack.pop();
+        Ok(())
+    }
+}
+
+/// Insert `#define` directives after the `#version` line, followed by a `#line`
+/// directive so that reported line numbers still match `source`.
+pub fn inject_defines(source: &str, defines: &[(&str, &str)]) -> String {
+    if defines.is_empty() {
+        return source.to_string();
+    }
+    let version_line = source
+        .lines()
+        .position(|line| line.trim_start().starts_with("#version"));
+    let split = version_line.map_or(0, |index| index + 1);
+
+    let mut output = String::with_capacity(source.len());
+    for line in source.lines().take(split) {
+        output.push_str(line);
+        output.push('\n');
+    }
+    for &(name, value) in defines {
+        output.push_str(&format!("#define {} {}\n", name, value));
+    }
+    output.push_str(&format!("#line {}\n", split + 1));
+    for line in source.lines().skip(split) {
+        output.push_str(line);
+        output.push('\n');
+    }
+    output
+}
+
+/// Expand the `#include` directives of the shader source in file `name`, and insert
+/// `defines` given as name and value pairs.
+///
+/// Included files are looked up with `resolver`, and files marked with `#pragma once`
+/// are only included the first time. `#line` directives are inserted around every
+/// included file, so that the line and source string number of compiler diagnostics
+/// refer to the original files, as listed in `PreprocessedSource::files`.
+///
+/// Includes in comments and in `#if` branches known not to be taken are left alone.
+/// Only conditions that are integer literals, or that test macros defined or undefined
+/// in the source or in `defines`, are evaluated.
+pub fn preprocess<R: IncludeResolver + ?Sized>(
+    name: &str,
+    source: &str,
+    defines: &[(&str, &str)],
+    resolver: &mut R,
+) -> Result<PreprocessedSource, PreprocessError> {
+    let mut expander = Expander {
+        resolver,
+        files: vec![name.to_string()],
+        stack: Vec::new(),
+        once: HashSet::new(),
+        macros: defines
+            .iter()
+            .map(|&(name, _)| (name.to_string(), true))
+            .collect(),
+        output: String::with_capacity(source.len()),
+    };
+    expander.expand(0, source)?;
+    Ok(PreprocessedSource {
+        source: inject_defines(&expander.output, defines),
+        files: expander.files,
+    })
+}
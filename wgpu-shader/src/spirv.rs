@@ -0,0 +1,74 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+
+pub const SPIRV_MAGIC: u32 = 0x0723_0203;
+
+/// Number of words in the header of a SPIR-V module.
+pub const HEADER_WORDS: usize = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpirvError {
+    /// The data is not a whole number of 4 byte words.
+    PartialWord,
+    /// The data is shorter than the 5 word module header.
+    TruncatedHeader,
+    /// The first word is not the SPIR-V magic number.
+    InvalidMagic(u32),
+}
+
+impl fmt::Display for SpirvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SpirvError::PartialWord => write!(f, "SPIR-V data is not a whole number of words"),
+            SpirvError::TruncatedHeader => write!(f, "SPIR-V data is shorter than its header"),
+            SpirvError::InvalidMagic(magic) => {
+                write!(f, "SPIR-V data starts with {:#010x} instead of the magic number", magic)
+            }
+        }
+    }
+}
+
+impl Error for SpirvError {}
+
+/// Check that `words` start with a complete SPIR-V header in native endianness.
+///
+/// Only the header is checked, the instructions are left to the driver.
+pub fn validate_spirv(words: &[u32]) -> Result<(), SpirvError> {
+    if words.len() < HEADER_WORDS {
+        return Err(SpirvError::TruncatedHeader);
+    }
+    match words[0] {
+        SPIRV_MAGIC => Ok(()),
+        magic => Err(SpirvError::InvalidMagic(magic)),
+    }
+}
+
+/// Read a SPIR-V module into words, swapping them if the module was written with the
+/// opposite endianness.
+///
+/// Data that fails `validate_spirv` is rejected with `io::ErrorKind::InvalidData`,
+/// wrapping a `SpirvError`.
+pub fn read_spirv<R: Read>(mut x: R) -> io::Result<Vec<u32>> {
+    let invalid = |err: SpirvError| io::Error::new(io::ErrorKind::InvalidData, err);
+
+    let mut bytes = Vec::new();
+    x.read_to_end(&mut bytes)?;
+    if bytes.len() % 4 != 0 {
+        return Err(invalid(SpirvError::PartialWord));
+    }
+
+    let mut words = bytes
+        .chunks(4)
+        .map(|b| {
+            u32::from(b[0]) | u32::from(b[1]) << 8 | u32::from(b[2]) << 16 | u32::from(b[3]) << 24
+        })
+        .collect::<Vec<_>>();
+    if words.first().map(|magic| magic.swap_bytes()) == Some(SPIRV_MAGIC) {
+        for word in &mut words {
+            *word = word.swap_bytes();
+        }
+    }
+    validate_spirv(&words).map_err(invalid)?;
+    Ok(words)
+}